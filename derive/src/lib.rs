@@ -0,0 +1,344 @@
+//! `#[derive(QueryValues)]` for nasa-rs parameter types
+//!
+//! Query keys follow the type's serde attributes, so a params type only
+//! describes its wire format once:
+//! - `#[serde(rename_all = "...")]` on a struct (`rename_all_fields` on an enum)
+//!   and `#[serde(rename = "...")]` on fields
+//! - `#[serde(skip)]`/`#[serde(skip_serializing)]` leave a field out
+//! - `#[serde(skip_serializing_if = "path")]` leaves a field out when `path(&field)` is true
+//! - `#[serde(flatten)]` merges a nested type's values
+//!
+//! Values are written by their serde representation, so `None` is left out and
+//! enums go on the wire under their serde names; a value serde cannot write is
+//! an `InvalidParameter` error naming the key. `#[query(...)]` takes the same
+//! `rename_all`, `rename`, `skip` and `flatten` keys, overriding serde, plus
+//! `with = "path"` for a custom `fn(&T) -> String` formatter (applied to the
+//! inner value of an `Option<T>`) and `default = "..."` for a value sent in
+//! place of `None`.
+//!
+//! Enums produce the values of whichever variant is set; unit variants add nothing.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parse_macro_input, Attribute, Data, DeriveInput, Field,
+    Fields, LitStr, Path, Type,
+};
+
+#[proc_macro_derive(QueryValues, attributes(query))]
+pub fn derive_query_values(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let is_enum = matches!(input.data, Data::Enum(_));
+    let rename_all = container_rename_all(&input.attrs, is_enum)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let Fields::Named(fields) = &data.fields else {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "QueryValues can only be derived for structs with named fields",
+                ));
+            };
+            let inserts = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.as_ref().unwrap();
+                    insert(field, rename_all, quote!(&self.#ident))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(#(#inserts)*)
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let name = &variant.ident;
+                    match &variant.fields {
+                        Fields::Unit => Ok(quote!(Self::#name => {})),
+                        Fields::Named(fields) => {
+                            let bindings: Vec<_> = fields
+                                .named
+                                .iter()
+                                .enumerate()
+                                .map(|(i, _)| format_ident!("__field{}", i))
+                                .collect();
+                            let idents = fields.named.iter().map(|field| &field.ident);
+                            let inserts = fields
+                                .named
+                                .iter()
+                                .zip(&bindings)
+                                .map(|(field, binding)| insert(field, rename_all, quote!(#binding)))
+                                .collect::<syn::Result<Vec<_>>>()?;
+                            Ok(quote! {
+                                Self::#name { #(#idents: #bindings),* } => { #(#inserts)* }
+                            })
+                        }
+                        Fields::Unnamed(_) => Err(syn::Error::new_spanned(
+                            variant,
+                            "QueryValues does not support tuple variants",
+                        )),
+                    }
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "QueryValues cannot be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::nasa_rs::query::QueryValues for #name #ty_generics #where_clause {
+            fn values(
+                &self,
+            ) -> ::nasa_rs::error::Result<
+                ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+            > {
+                let mut map = ::std::collections::HashMap::new();
+                #body
+                ::std::result::Result::Ok(map)
+            }
+        }
+    })
+}
+
+/// Code adding one field, reachable through `access` (a reference), to `map`
+fn insert(
+    field: &Field,
+    rename_all: Option<RenameRule>,
+    access: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let attrs = FieldAttrs::parse(&field.attrs)?;
+    if attrs.skip {
+        return Ok(quote!());
+    }
+    if attrs.flatten {
+        return Ok(quote! {
+            map.extend(::nasa_rs::query::QueryValues::values(#access)?);
+        });
+    }
+
+    // `r#type` goes on the wire as `type`, as serde writes it
+    let ident = field.ident.as_ref().unwrap().unraw().to_string();
+    let key = match attrs.rename {
+        Some(rename) => rename,
+        None => rename_all.map_or_else(|| ident.clone(), |rule| rule.apply(&ident)),
+    };
+    let otherwise = match &attrs.default {
+        Some(default) => quote! {
+            else {
+                map.insert(#key.to_owned(), #default.to_owned());
+            }
+        },
+        None => quote!(),
+    };
+    let insert = match (&attrs.with, is_option(&field.ty)) {
+        (Some(with), true) => quote! {
+            if let ::std::option::Option::Some(value) = value {
+                map.insert(#key.to_owned(), #with(value));
+            } #otherwise
+        },
+        (Some(with), false) => quote! {
+            map.insert(#key.to_owned(), #with(value));
+        },
+        (None, _) => quote! {
+            if let ::std::option::Option::Some(value) = ::nasa_rs::query::to_value(#key, value)? {
+                map.insert(#key.to_owned(), value);
+            } #otherwise
+        },
+    };
+    let insert = match &attrs.skip_if {
+        Some(skip_if) => quote! {
+            if !#skip_if(value) {
+                #insert
+            }
+        },
+        None => insert,
+    };
+    Ok(quote! {
+        {
+            let value = #access;
+            #insert
+        }
+    })
+}
+
+/// Whether `ty` is spelled `Option<...>`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// The rule for field names: `#[query(rename_all)]`, or else serde's
+/// `rename_all` (`rename_all_fields` for enums, where `rename_all` renames variants)
+fn container_rename_all(attrs: &[Attribute], is_enum: bool) -> syn::Result<Option<RenameRule>> {
+    let serde_key = if is_enum {
+        "rename_all_fields"
+    } else {
+        "rename_all"
+    };
+    let mut serde = None;
+    let mut query = None;
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident(serde_key) && meta.input.peek(syn::Token![=]) {
+                    serde = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    ignore(&meta)
+                }
+            })?;
+        } else if attr.path().is_ident("query") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    query = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename_all`"))
+                }
+            })?;
+        }
+    }
+    Ok(query.or(serde))
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    skip_if: Option<Path>,
+    flatten: bool,
+    with: Option<Path>,
+    default: Option<String>,
+}
+
+impl FieldAttrs {
+    /// `#[serde]` attributes, then `#[query]` attributes on top
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                    parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("skip_serializing_if") {
+                    parsed.skip_if = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("flatten") {
+                    parsed.flatten = true;
+                } else {
+                    ignore(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("query")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    parsed.flatten = true;
+                } else if meta.path.is_ident("with") {
+                    parsed.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("default") {
+                    parsed.default = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    return Err(
+                        meta.error("expected `rename`, `skip`, `flatten`, `with` or `default`")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Consume a serde key this derive does not use, e.g. `default` or `with = "..."`
+fn ignore(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Lit>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|inner| ignore(&inner))?;
+    }
+    Ok(())
+}
+
+/// serde's `rename_all` conventions, applied to snake_case field names
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(rule: &LitStr) -> syn::Result<Self> {
+        Ok(match rule.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return Err(syn::Error::new_spanned(rule, "unknown rename_all rule")),
+        })
+    }
+
+    fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_owned(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal | Self::Camel => {
+                let mut renamed = String::with_capacity(field.len());
+                let mut capitalize = matches!(self, Self::Pascal);
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = !renamed.is_empty();
+                    } else if capitalize {
+                        renamed.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        renamed.push(ch);
+                    }
+                }
+                renamed
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use nasa_rs::{params::ApodParams, prelude::*, Apod};
+use nasa_rs::{params::ApodParams, Apod};
 
 fn main() {
     let client = Apod::default();
     // Use today's date
     let params = ApodParams::default();
-    let response = client.query(&params).unwrap();
-    println!("{}", response);
+    let entry = client.entry(&params).unwrap();
+    println!("{} ({})", entry.title, entry.date);
+    if let Some(url) = entry.hdurl.or(entry.url) {
+        println!("{}", url);
+    }
 }
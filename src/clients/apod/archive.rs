@@ -0,0 +1,214 @@
+use super::{Apod, ApodEntry, ApodParams, MediaType};
+use crate::error::{Error, Result};
+use crate::prelude::Client;
+use std::path::{Path, PathBuf};
+
+/// Which file to keep for an entry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Quality {
+    /// `url` for images, `thumbnail_url` for videos
+    #[default]
+    Standard,
+    /// `hdurl` (falling back to `url`) for images, `thumbnail_url` for videos
+    Hd,
+}
+
+/// What happened to an entry when archiving it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Archived {
+    /// The media was downloaded into `path`
+    Downloaded { path: PathBuf },
+    /// `path` already existed, nothing was downloaded
+    Present { path: PathBuf },
+    /// Nothing to download, e.g. a video queried without `thumbs`
+    Unavailable { reason: String },
+}
+
+/// A directory of APOD media with a JSON metadata sidecar per date
+///
+/// Files are named after the entry's date: `2024-01-01.jpg` and
+/// `2024-01-01.json`, with HD images kept apart as `2024-01-01-hd.jpg`. Media
+/// that is already present is never downloaded again, so archiving an
+/// overlapping range only fetches what is missing.
+#[derive(Debug, Clone)]
+pub struct ApodArchive {
+    dir: PathBuf,
+    quality: Quality,
+}
+
+impl ApodArchive {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            quality: Quality::default(),
+        }
+    }
+
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The url that would be downloaded for `entry`
+    pub fn media_url<'a>(&self, entry: &'a ApodEntry) -> Option<&'a str> {
+        match (entry.media_type, self.quality) {
+            (MediaType::Image, Quality::Hd) => entry.hdurl.as_deref().or(entry.url.as_deref()),
+            (MediaType::Image, Quality::Standard) => entry.url.as_deref(),
+            (MediaType::Video, _) => entry.thumbnail_url.as_deref(),
+            (MediaType::Other, _) => None,
+        }
+    }
+
+    /// Where the media for `entry` is stored; `-hd` marks images downloaded from `hdurl`
+    pub fn media_path(&self, entry: &ApodEntry) -> Option<PathBuf> {
+        let url = self.media_url(entry)?;
+        let file = url.split(['?', '#']).next().unwrap_or(url);
+        let extension = file
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .filter(|extension| !extension.is_empty() && extension.len() <= 5)
+            .unwrap_or_else(|| "jpg".to_owned());
+        let hd = self.quality == Quality::Hd
+            && entry.media_type == MediaType::Image
+            && entry.hdurl.is_some();
+        let suffix = if hd { "-hd" } else { "" };
+        Some(
+            self.dir
+                .join(format!("{}{}.{}", entry.date, suffix, extension)),
+        )
+    }
+
+    /// Where the metadata for `entry` is stored
+    pub fn sidecar_path(&self, entry: &ApodEntry) -> PathBuf {
+        self.dir.join(format!("{}.json", entry.date))
+    }
+
+    /// Write `entry`'s metadata and download its media unless already present
+    pub fn save(&self, client: &Client<Apod>, entry: &ApodEntry) -> Result<Archived> {
+        std::fs::create_dir_all(&self.dir)?;
+        let sidecar = self.sidecar_path(entry);
+        if !sidecar.exists() {
+            write_atomic(&sidecar, &serde_json::to_vec_pretty(entry)?)?;
+        }
+
+        let (Some(url), Some(path)) = (self.media_url(entry), self.media_path(entry)) else {
+            let reason = match entry.media_type {
+                MediaType::Video => "video without a thumbnail; query with thumbs(true)",
+                _ => "no downloadable media",
+            };
+            return Ok(Archived::Unavailable {
+                reason: reason.to_owned(),
+            });
+        };
+        if path.exists() {
+            log::debug!("{} already archived", entry.date);
+            return Ok(Archived::Present { path });
+        }
+
+        log::debug!("Downloading {} into {}", url, path.display());
+        let response = client.fetch(url)?;
+        write_atomic(&path, &response.body)?;
+        Ok(Archived::Downloaded { path })
+    }
+}
+
+impl Client<Apod> {
+    /// Download the media for a single entry into `archive`
+    pub fn download(&self, entry: &ApodEntry, archive: &ApodArchive) -> Result<Archived> {
+        archive.save(self, entry)
+    }
+
+    /// Query `params` and archive every entry, skipping media already present
+    pub fn archive(
+        &self,
+        params: &ApodParams,
+        archive: &ApodArchive,
+    ) -> Result<Vec<(ApodEntry, Archived)>> {
+        self.entries(params)?
+            .into_iter()
+            .map(|entry| {
+                let archived = archive.save(self, &entry)?;
+                Ok((entry, archived))
+            })
+            .collect()
+    }
+}
+
+/// Write through a temporary file so an interrupted download is never mistaken
+/// for a complete one
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+    std::fs::write(&partial, contents)?;
+    std::fs::rename(&partial, path).map_err(Error::from)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::{Request, Response, Transport};
+    use chrono::NaiveDate;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn archives_range_once() {
+        let downloads = Arc::new(AtomicUsize::new(0));
+        let counter = downloads.clone();
+        let cassette = crate::transport::test_cassette();
+        let client = Client::<Apod>::builder()
+            .transport(move |request: &Request| {
+                if request.url.starts_with("https://apod.nasa.gov/") {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    return Ok(Response {
+                        body: request.url.as_bytes().to_vec(),
+                        ..Response::json_body("")
+                    });
+                }
+                cassette.send(request)
+            })
+            .build()
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("nasa-rs-apod-{}", std::process::id()));
+        let archive = ApodArchive::new(&dir).quality(Quality::Hd);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+        let params = ApodParams::range(start, end);
+
+        let first = client.archive(&params, &archive).unwrap();
+        assert_eq!(downloads.load(Ordering::SeqCst), 2);
+        assert!(matches!(first[0].1, Archived::Downloaded { .. }));
+        assert!(matches!(first[1].1, Archived::Unavailable { .. }));
+
+        let image = dir.join("2024-01-01-hd.jpg");
+        let contents = std::fs::read_to_string(&image).unwrap();
+        assert!(contents.ends_with("OrionDust_Lacroce_4000.jpg"));
+        let sidecar: ApodEntry =
+            serde_json::from_slice(&std::fs::read(dir.join("2024-01-02.json")).unwrap()).unwrap();
+        assert_eq!(sidecar, first[1].0);
+
+        let second = client.archive(&params, &archive).unwrap();
+        assert_eq!(downloads.load(Ordering::SeqCst), 2);
+        assert_eq!(second[0].1, Archived::Present { path: image });
+
+        // standard quality doesn't mistake the HD image for its own
+        let standard = ApodArchive::new(&dir);
+        let third = client.archive(&params, &standard).unwrap();
+        assert_eq!(downloads.load(Ordering::SeqCst), 4);
+        assert_eq!(
+            third[0].1,
+            Archived::Downloaded {
+                path: dir.join("2024-01-01.jpg")
+            }
+        );
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Kind of media an APOD entry links to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaType {
+    Image,
+    Video,
+    /// Anything else, e.g. interactive pages
+    #[serde(other)]
+    Other,
+}
+
+/// A single Astronomy Picture of the Day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApodEntry {
+    pub date: NaiveDate,
+    pub title: String,
+    pub explanation: String,
+    pub media_type: MediaType,
+    /// The image, or the embed url for videos; missing for some `other` entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Full resolution image, for images only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hdurl: Option<String>,
+    /// Video thumbnail, only when queried with `thumbs=true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    /// Missing for public domain images
+    #[serde(
+        default,
+        deserialize_with = "trimmed",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub copyright: Option<String>,
+    pub service_version: String,
+}
+
+/// The API returns a single object for `date` queries and an array for
+/// `count` and date-range queries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ApodResponse {
+    Single(ApodEntry),
+    Many(Vec<ApodEntry>),
+}
+
+impl ApodResponse {
+    /// All entries, whichever shape the API answered with
+    pub fn into_entries(self) -> Vec<ApodEntry> {
+        match self {
+            Self::Single(entry) => vec![entry],
+            Self::Many(entries) => entries,
+        }
+    }
+}
+
+impl From<ApodResponse> for Vec<ApodEntry> {
+    fn from(response: ApodResponse) -> Self {
+        response.into_entries()
+    }
+}
+
+/// The API pads copyright lines with newlines
+fn trimmed<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty()))
+}
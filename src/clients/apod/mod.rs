@@ -0,0 +1,244 @@
+use crate::{error::Error, prelude::*, query::QueryValues};
+use chrono::NaiveDate;
+
+/// Downloading APOD media into a local archive
+pub mod archive;
+mod entry;
+pub use archive::{ApodArchive, Archived, Quality};
+pub use entry::{ApodEntry, ApodResponse, MediaType};
+
+/// Astronomy Picture of the Day
+pub struct Apod;
+
+/// Query parameters for the APOD API
+///
+/// Built with [ApodParams::date], [ApodParams::range] or [ApodParams::random];
+/// the default queries today's picture
+#[derive(serde::Serialize, Debug, Default, Clone, PartialEq, Eq, QueryValues)]
+pub struct ApodParams {
+    /// The date of the APOD image to retrieve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<NaiveDate>,
+    /// The start date of the APOD images to retrieve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<NaiveDate>,
+    /// The end date of the APOD images to retrieve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<NaiveDate>,
+    /// The number of APOD images to retrieve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<u32>,
+    /// The thumbsize of the APOD image to retrieve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbs: Option<bool>,
+}
+
+impl ApodParams {
+    /// The first Astronomy Picture of the Day
+    pub const FIRST_DATE: NaiveDate = NaiveDate::from_ymd_opt(1995, 6, 16).unwrap();
+    /// Most entries the API returns for a `count` query
+    pub const MAX_COUNT: u32 = 100;
+
+    /// The picture for a single date
+    pub fn date(date: NaiveDate) -> Self {
+        Self {
+            date: Some(date),
+            ..Default::default()
+        }
+    }
+
+    /// Every picture from `start` to `end`, inclusive
+    pub fn range(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            end_date: Some(end),
+            ..Default::default()
+        }
+    }
+
+    /// Every picture from `start` to today
+    pub fn since(start: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            ..Default::default()
+        }
+    }
+
+    /// `count` randomly chosen pictures
+    pub fn random(count: u32) -> Self {
+        Self {
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    /// Include `thumbnail_url` for video entries
+    pub fn thumbs(mut self, thumbs: bool) -> Self {
+        self.thumbs = Some(thumbs);
+        self
+    }
+
+    /// Reject combinations the API refuses, before a request is sent
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |name, reason: &str| {
+            Err(Error::InvalidParameter {
+                name,
+                reason: reason.to_owned(),
+            })
+        };
+        if self.date.is_some() && (self.start_date.is_some() || self.end_date.is_some()) {
+            return invalid("date", "cannot be combined with start_date/end_date");
+        }
+        if let Some(count) = self.count {
+            if self.date.is_some() || self.start_date.is_some() || self.end_date.is_some() {
+                return invalid(
+                    "count",
+                    "cannot be combined with date, start_date or end_date",
+                );
+            }
+            if count == 0 || count > Self::MAX_COUNT {
+                return invalid("count", "must be between 1 and 100");
+            }
+        }
+        if self.end_date.is_some() && self.start_date.is_none() {
+            return invalid("end_date", "requires start_date");
+        }
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            if start > end {
+                return invalid("start_date", "must not be after end_date");
+            }
+        }
+        let today = chrono::Utc::now().date_naive();
+        for (name, date) in [
+            ("date", self.date),
+            ("start_date", self.start_date),
+            ("end_date", self.end_date),
+        ] {
+            match date {
+                Some(date) if date < Self::FIRST_DATE => {
+                    return invalid(name, "must not be before 1995-06-16");
+                }
+                Some(date) if date > today => return invalid(name, "must not be in the future"),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Spec for Apod {
+    const BASE_URL: &'static str = "https://api.nasa.gov/planetary/apod?";
+    type Params = ApodParams;
+    type ResponseType = ApodResponse;
+
+    fn validate(params: &ApodParams) -> crate::error::Result<()> {
+        params.validate()
+    }
+}
+
+// Implement Client Handling for Apod
+impl ClientHandler<Apod> for Client<Apod> {}
+
+impl Client<Apod> {
+    /// Query a single date
+    pub fn entry(&self, params: &ApodParams) -> crate::error::Result<ApodEntry> {
+        match self.query(params)? {
+            ApodResponse::Single(entry) => Ok(entry),
+            ApodResponse::Many(_) => Err(Error::Decode(serde::de::Error::custom(
+                "expected a single APOD entry, got a list",
+            ))),
+        }
+    }
+
+    /// Query a `count` or date range
+    pub fn entries(&self, params: &ApodParams) -> crate::error::Result<Vec<ApodEntry>> {
+        Ok(self.query(params)?.into_entries())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::ymd;
+
+    #[test]
+    fn test_apod() {
+        pretty_env_logger::try_init().ok();
+
+        let client = crate::transport::test_client::<Apod>();
+        let params = ApodParams::date(ymd(2024, 1, 1));
+        let response = client.entry(&params).unwrap();
+        let prtty = serde_json::to_string_pretty(&response).unwrap();
+        println!("{}", prtty);
+        assert_eq!(response.date, ymd(2024, 1, 1));
+        assert_eq!(response.media_type, MediaType::Image);
+        assert_eq!(response.copyright.as_deref(), Some("Gianni Lacroce"));
+        assert!(response.hdurl.is_some());
+    }
+
+    #[test]
+    fn test_apod_range() {
+        let client = crate::transport::test_client::<Apod>();
+        let params = ApodParams::range(ymd(2024, 1, 1), ymd(2024, 1, 3));
+        let entries = client.entries(&params).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].media_type, MediaType::Video);
+        assert!(entries[1].hdurl.is_none() && entries[1].copyright.is_none());
+        assert!(client.entry(&params).is_err());
+    }
+
+    #[test]
+    fn test_apod_count() {
+        let client = crate::transport::test_client::<Apod>();
+        let params = ApodParams::random(2).thumbs(true);
+        let entries = client.entries(&params).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].thumbnail_url.is_some());
+        assert_eq!(entries[1].media_type, MediaType::Other);
+        assert!(entries[1].url.is_none());
+    }
+
+    #[test]
+    fn values_keep_every_field() {
+        let values = ApodParams::range(ymd(2024, 1, 1), ymd(2024, 1, 3))
+            .thumbs(true)
+            .values()
+            .unwrap();
+        assert_eq!(values["start_date"], "2024-01-01");
+        assert_eq!(values["end_date"], "2024-01-03");
+        assert_eq!(values["thumbs"], "true");
+        assert!(ApodParams::default().values().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_combinations() {
+        let invalid = |params: ApodParams| {
+            matches!(
+                params.validate(),
+                Err(crate::error::Error::InvalidParameter { .. })
+            )
+        };
+        assert!(invalid(ApodParams {
+            count: Some(3),
+            ..ApodParams::date(ymd(2024, 1, 1))
+        }));
+        assert!(invalid(ApodParams {
+            date: Some(ymd(2024, 1, 1)),
+            ..ApodParams::since(ymd(2023, 12, 1))
+        }));
+        assert!(invalid(ApodParams::date(ymd(1995, 6, 15))));
+        assert!(invalid(ApodParams::range(ymd(2024, 1, 3), ymd(2024, 1, 1))));
+        assert!(invalid(ApodParams::random(0)));
+        assert!(invalid(ApodParams::random(101)));
+        assert!(ApodParams::date(ApodParams::FIRST_DATE).validate().is_ok());
+        assert!(ApodParams::default().validate().is_ok());
+
+        // rejected before the (unrecorded) request is sent
+        let client = crate::transport::test_client::<Apod>();
+        let params = ApodParams::date(ymd(1990, 1, 1));
+        assert!(matches!(
+            client.query(&params),
+            Err(crate::error::Error::InvalidParameter { name: "date", .. })
+        ));
+    }
+}
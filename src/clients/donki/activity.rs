@@ -0,0 +1,175 @@
+use super::models::EventKind;
+use crate::error::Error;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A DONKI activity ID such as `2024-05-10T17:36:00-CME-001`
+///
+/// Ordered by time, then event type, then sequence number. Formats back to
+/// the ID it was parsed from, keeping the sequence's zero padding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActivityId {
+    time: DateTime<Utc>,
+    kind: EventKind,
+    sequence: u32,
+    /// Digits the sequence is written with, zero-padded
+    width: u8,
+}
+
+impl ActivityId {
+    const TIME_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S";
+    /// Sequence digits in IDs DONKI issues
+    const SEQUENCE_WIDTH: u8 = 3;
+
+    /// An ID with the usual three-digit sequence, e.g. `...-GST-012`
+    pub fn new(time: DateTime<Utc>, kind: EventKind, sequence: u32) -> Self {
+        Self {
+            time,
+            kind,
+            sequence,
+            width: Self::SEQUENCE_WIDTH,
+        }
+    }
+
+    /// When the activity began
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    /// Distinguishes activities of the same type starting at the same time; usually 1
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    /// Every activity ID mentioned in `text`, in order of appearance
+    pub fn find_all(text: &str) -> Vec<ActivityId> {
+        text.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '(' | ')' | '"'))
+            .filter_map(|word| word.trim_end_matches('.').parse().ok())
+            .collect()
+    }
+}
+
+impl FromStr for ActivityId {
+    type Err = Error;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidParameter {
+            name: "activityID",
+            reason: format!("{id:?} is not of the form 2024-05-10T17:36:00-CME-001"),
+        };
+        // the timestamp contains dashes itself, so split from the right
+        let mut parts = id.rsplitn(3, '-');
+        let (Some(sequence), Some(kind), Some(time)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if sequence.is_empty() || !sequence.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let width = u8::try_from(sequence.len()).map_err(|_| invalid())?;
+        Ok(Self {
+            time: NaiveDateTime::parse_from_str(time, Self::TIME_FORMAT)
+                .map_err(|_| invalid())?
+                .and_utc(),
+            kind: kind.parse().map_err(|_| invalid())?,
+            sequence: sequence.parse().map_err(|_| invalid())?,
+            width,
+        })
+    }
+}
+
+impl fmt::Display for ActivityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{:0width$}",
+            self.time.format(Self::TIME_FORMAT),
+            self.kind,
+            self.sequence,
+            width = self.width as usize
+        )
+    }
+}
+
+impl Serialize for ActivityId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ActivityId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        id.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_and_formats() {
+        let id: ActivityId = "2024-05-10T17:36:00-CME-001".parse().unwrap();
+        assert_eq!(
+            id.time(),
+            Utc.with_ymd_and_hms(2024, 5, 10, 17, 36, 0).unwrap()
+        );
+        assert_eq!(id.kind(), EventKind::Cme);
+        assert_eq!(id.sequence(), 1);
+        assert_eq!(id.to_string(), "2024-05-10T17:36:00-CME-001");
+        assert_eq!(
+            ActivityId::new(id.time(), EventKind::Gst, 12).to_string(),
+            "2024-05-10T17:36:00-GST-012"
+        );
+
+        // IDs print exactly as received, whatever their padding
+        for (received, sequence) in [
+            ("2024-05-10T17:36:00-CME-1", 1),
+            ("2024-05-10T17:36:00-CME-01", 1),
+            ("2024-05-10T17:36:00-CME-1000", 1000),
+            ("2024-05-10T17:36:00-CME-0001", 1),
+        ] {
+            let id: ActivityId = received.parse().unwrap();
+            assert_eq!(id.sequence(), sequence);
+            assert_eq!(id.to_string(), received);
+        }
+
+        for bad in [
+            "",
+            "2024-05-10T17:36:00-CME",
+            "2024-05-10T17:36:00-XYZ-001",
+            "2024-05-10-CME-001",
+            "2024-05-10T17:36:00-CME-1a",
+        ] {
+            assert!(bad.parse::<ActivityId>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn works_as_key_and_field() {
+        let ids: Vec<ActivityId> = serde_json::from_str(
+            r#"["2024-05-10T15:00:00-GST-001","2024-05-08T04:37:00-FLR-001"]"#,
+        )
+        .unwrap();
+        let sorted: std::collections::BTreeSet<_> = ids.iter().copied().collect();
+        assert_eq!(sorted.first().unwrap().kind(), EventKind::Flr);
+        assert_eq!(
+            serde_json::to_string(&ids[0]).unwrap(),
+            r#""2024-05-10T15:00:00-GST-001""#
+        );
+        assert!(serde_json::from_str::<ActivityId>(r#""not an id""#).is_err());
+
+        let body = "Activity ID: 2024-05-08T05:36:00-CME-001 (associated with 2024-05-08T04:37:00-FLR-001).";
+        let found = ActivityId::find_all(body);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].kind(), EventKind::Flr);
+    }
+}
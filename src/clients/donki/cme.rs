@@ -1,97 +1,262 @@
 #![allow(non_camel_case_types)]
-use super::endpoints::CME_BASE_URL;
-use crate::{prelude::*, query::QueryValues};
+use super::endpoints::{CME_ANALYSIS_BASE_URL, CME_BASE_URL};
+use super::models::CmeEvent;
+use crate::{params::ParamsCommon, prelude::*, query::QueryValues};
+use chrono::NaiveDate;
 use serde::Serialize;
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum Catalog {
-    ALL,
-    SWRC_CATALOG,
-    JANG_ET_AL_CATALOG,
+crate::query_enum! {
+    /// Catalog a CME analysis is listed in
+    pub enum Catalog("catalog") {
+        ALL = "ALL",
+        SWRC_CATALOG = "SWRC_CATALOG",
+        JANG_ET_AL_CATALOG = "JANG_ET_AL_CATALOG",
+    }
+}
+
+crate::query_enum! {
+    /// Keyword a CME analysis is tagged with
+    pub enum Keyword("keyword") {
+        NONE = "NONE",
+        SWPC_ANNEX = "swpc_annex",
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Query parameters for the DONKI CME Analysis API
+///
+/// Without dates the API searches the last
+/// [DONKI_DEFAULT_WINDOW_DAYS](crate::params::DONKI_DEFAULT_WINDOW_DAYS) days
+#[derive(Debug, Default, Clone, Serialize, QueryValues)]
 #[serde(rename_all = "camelCase")]
 pub struct CmeAnalysisParams {
     /// default 30 days prior to current UTC time
-    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<NaiveDate>,
     /// default is set to current UTC time
-    end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<NaiveDate>,
     /// default is set to true
+    #[serde(skip_serializing_if = "Option::is_none")]
     most_accurate_only: Option<bool>,
     /// default is set to true
+    #[serde(skip_serializing_if = "Option::is_none")]
     complete_entry_only: Option<bool>,
     /// (lower limit) default is set to 0
+    #[serde(skip_serializing_if = "Option::is_none")]
     speed: Option<f64>,
     /// (lower limit) default is set to 0
+    #[serde(skip_serializing_if = "Option::is_none")]
     half_angle: Option<f64>,
     /// default is set to ALL; see [Catalog](enum.Catalog.html) for more info
+    #[serde(skip_serializing_if = "Option::is_none")]
     catalog: Option<Catalog>,
-    /// default is set to NONE (example choices: swpc_annex)
-    keyword: Option<String>,
+    /// default is set to NONE; see [Keyword](enum.Keyword.html)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keyword: Option<Keyword>,
 }
 
-impl Default for CmeAnalysisParams {
-    fn default() -> Self {
-        CmeAnalysisParams {
-            start_date: None,
-            end_date: None,
-            most_accurate_only: None,
-            complete_entry_only: None,
-            speed: None,
-            half_angle: None,
-            catalog: None,
-            keyword: None,
+impl CmeAnalysisParams {
+    /// Analyses from `start` to `end`, inclusive
+    pub fn range(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            end_date: Some(end),
+            ..Default::default()
         }
     }
-}
 
-impl QueryValues for CmeAnalysisParams {
-    fn values(&self) -> std::collections::HashMap<String, String> {
-        let mut map = std::collections::HashMap::new();
-        if let Some(start_date) = &self.start_date {
-            map.insert("startDate".to_string(), start_date.to_string());
-        }
-        if let Some(end_date) = &self.end_date {
-            map.insert("endDate".to_string(), end_date.to_string());
-        }
-        if let Some(most_accurate_only) = self.most_accurate_only {
-            map.insert(
-                "mostAccurateOnly".to_string(),
-                most_accurate_only.to_string(),
-            );
+    /// Analyses from `start` to today
+    pub fn since(start: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            ..Default::default()
         }
-        if let Some(complete_entry_only) = self.complete_entry_only {
-            map.insert(
-                "completeEntryOnly".to_string(),
-                complete_entry_only.to_string(),
-            );
-        }
-        if let Some(speed) = self.speed {
-            map.insert("speed".to_string(), speed.to_string());
-        }
-        if let Some(half_angle) = self.half_angle {
-            map.insert("halfAngle".to_string(), half_angle.to_string());
-        }
-        if let Some(catalog) = &self.catalog {
-            map.insert("catalog".to_string(), format!("{:?}", catalog));
-        }
-        if let Some(keyword) = &self.keyword {
-            map.insert("keyword".to_string(), keyword.to_owned());
+    }
+
+    /// Only the most accurate analysis of each CME
+    pub fn most_accurate_only(mut self, most_accurate_only: bool) -> Self {
+        self.most_accurate_only = Some(most_accurate_only);
+        self
+    }
+
+    /// Only analyses with every field filled in
+    pub fn complete_entry_only(mut self, complete_entry_only: bool) -> Self {
+        self.complete_entry_only = Some(complete_entry_only);
+        self
+    }
+
+    /// Minimum speed, in km/s
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Minimum half angle, in degrees
+    pub fn half_angle(mut self, half_angle: f64) -> Self {
+        self.half_angle = Some(half_angle);
+        self
+    }
+
+    pub fn catalog(mut self, catalog: Catalog) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    pub fn keyword(mut self, keyword: Keyword) -> Self {
+        self.keyword = Some(keyword);
+        self
+    }
+
+    /// The dates the API will search, with defaults filled in
+    pub fn window(&self) -> (NaiveDate, NaiveDate) {
+        crate::params::donki_window(self.start_date, self.end_date)
+    }
+
+    /// Reject a range that ends before it starts, or negative lower limits
+    pub fn validate(&self) -> crate::error::Result<()> {
+        for (name, limit) in [("speed", self.speed), ("halfAngle", self.half_angle)] {
+            if limit.is_some_and(|limit| limit.is_nan() || limit < 0.0) {
+                return Err(crate::error::Error::InvalidParameter {
+                    name,
+                    reason: "must not be negative".to_owned(),
+                });
+            }
         }
-        map
+        crate::params::validate_donki_window(self.start_date, self.end_date)
     }
 }
 
 /// CME (Coronal Mass Ejection) is a large release of plasma and magnetic field from the solar corona.
+pub struct Cme;
+
+impl Spec for Cme {
+    const BASE_URL: &'static str = CME_BASE_URL;
+    type Params = ParamsCommon;
+    type ResponseType = Vec<CmeEvent>;
+
+    fn validate(params: &ParamsCommon) -> crate::error::Result<()> {
+        params.validate()
+    }
+}
+
+// Implement Client Handling for Cme
+impl ClientHandler<Cme> for Client<Cme> {}
+
+/// Speed and direction analyses of CMEs, filterable by speed, angle and catalog
 pub struct CmeAnalysis;
 
 impl Spec for CmeAnalysis {
-    const BASE_URL: &'static str = CME_BASE_URL;
+    const BASE_URL: &'static str = CME_ANALYSIS_BASE_URL;
     type Params = CmeAnalysisParams;
 
-    fn parse_response(res: reqwest::blocking::Response) -> Self::ResponseType {
-        res.json().unwrap()
+    fn validate(params: &CmeAnalysisParams) -> crate::error::Result<()> {
+        params.validate()
+    }
+}
+
+// Implement Client Handling for CmeAnalysis
+impl ClientHandler<CmeAnalysis> for Client<CmeAnalysis> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_cme() {
+        let client = crate::transport::test_client::<Cme>();
+        let events = client.query(&ParamsCommon::default()).unwrap();
+        assert_eq!(events.len(), 2);
+
+        let halo = &events[0];
+        assert_eq!(halo.activity_id.to_string(), "2024-05-08T05:36:00-CME-001");
+        assert_eq!(
+            halo.start_time,
+            Utc.with_ymd_and_hms(2024, 5, 8, 5, 36, 0).unwrap()
+        );
+        assert_eq!(halo.active_region_num, Some(13664));
+        assert_eq!(halo.instruments.len(), 3);
+        let linked: Vec<_> = halo
+            .linked_events
+            .iter()
+            .map(|event| event.activity_id.to_string())
+            .collect();
+        assert_eq!(
+            linked,
+            vec![
+                "2024-05-08T04:37:00-FLR-001",
+                "2024-05-10T16:36:00-IPS-001",
+                "2024-05-10T15:00:00-GST-001"
+            ]
+        );
+        let analysis = halo.most_accurate_analysis().unwrap();
+        assert_eq!(analysis.speed, Some(1128.0));
+        assert_eq!(analysis.enlil_list[0].impact_list[0].location, "Earth");
+        assert_eq!(analysis.enlil_list[0].kp_90, Some(8));
+
+        // DONKI sends `null` and `""` for missing data
+        let faint = &events[1];
+        assert!(faint.cme_analyses.is_empty() && faint.linked_events.is_empty());
+        assert!(faint.source_location.is_none() && faint.active_region_num.is_none());
+    }
+
+    #[test]
+    fn test_cme_analysis() {
+        let client = crate::transport::test_client::<CmeAnalysis>();
+        let analyses = client.query(&CmeAnalysisParams::default()).unwrap();
+        assert_eq!(
+            analyses[0]["associatedCMEID"],
+            "2024-05-08T05:36:00-CME-001"
+        );
+    }
+
+    #[test]
+    fn builds_analysis_params() {
+        let ymd = |day| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let params = CmeAnalysisParams::range(ymd(8), ymd(12))
+            .speed(500.0)
+            .keyword(Keyword::SWPC_ANNEX);
+        let values = params.values().unwrap();
+        assert_eq!(values["startDate"], "2024-05-08");
+        assert_eq!(values["endDate"], "2024-05-12");
+        assert_eq!(values["speed"], "500");
+        assert_eq!(values["keyword"], "swpc_annex");
+        assert_eq!(params.window(), (ymd(8), ymd(12)));
+        assert!(params.validate().is_ok());
+
+        assert!(CmeAnalysisParams::range(ymd(12), ymd(8))
+            .validate()
+            .is_err());
+        assert!(CmeAnalysisParams::default()
+            .half_angle(-1.0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn analysis_query_and_query_with_agree() {
+        assert_eq!(
+            "swpc_annex".parse::<Keyword>().unwrap(),
+            Keyword::SWPC_ANNEX
+        );
+        assert_eq!(
+            serde_json::from_str::<Catalog>("\"JANG_ET_AL_CATALOG\"").unwrap(),
+            Catalog::JANG_ET_AL_CATALOG
+        );
+
+        let params = CmeAnalysisParams::default()
+            .catalog(Catalog::SWRC_CATALOG)
+            .keyword(Keyword::SWPC_ANNEX)
+            .most_accurate_only(true);
+        let (client, urls) = crate::transport::recording_client::<CmeAnalysis>();
+        client.query(&params).unwrap();
+        client.query_with(params.clone()).unwrap();
+        let urls = urls.lock().unwrap();
+        assert_eq!(
+            urls[0],
+            "https://api.nasa.gov/DONKI/CMEAnalysis?catalog=SWRC_CATALOG&keyword=swpc_annex\
+             &mostAccurateOnly=true&api_key=test-key"
+        );
+        assert_eq!(urls[0], urls[1]);
     }
 }
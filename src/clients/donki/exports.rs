@@ -1,15 +1,27 @@
 use super::endpoints::*;
+use super::models::*;
 use crate::create_client_impl;
+use crate::params::ParamsCommon;
 use crate::prelude::{Client, ClientHandler};
 
-create_client_impl!(Flr, FLR_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Gst, GST_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Sep, SEP_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Mpc, MPC_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Rbe, RBE_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Hss, HSS_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Wsa, WSA_BASE_URL, crate::params::ParamsCommon);
-create_client_impl!(Ips, IPS_BASE_URL, super::ips::IpsParams);
+create_client_impl!(Flr, FLR_BASE_URL, ParamsCommon, Vec<FlareEvent>);
+create_client_impl!(Gst, GST_BASE_URL, ParamsCommon, Vec<GeomagneticStorm>);
+create_client_impl!(Sep, SEP_BASE_URL, ParamsCommon, Vec<SepEvent>);
+create_client_impl!(Mpc, MPC_BASE_URL, ParamsCommon, Vec<MagnetopauseCrossing>);
+create_client_impl!(
+    Rbe,
+    RBE_BASE_URL,
+    ParamsCommon,
+    Vec<RadiationBeltEnhancement>
+);
+create_client_impl!(Hss, HSS_BASE_URL, ParamsCommon, Vec<HighSpeedStream>);
+create_client_impl!(Wsa, WSA_BASE_URL, ParamsCommon, Vec<WsaEnlilSimulation>);
+create_client_impl!(
+    Ips,
+    IPS_BASE_URL,
+    super::ips::IpsParams,
+    Vec<InterplanetaryShock>
+);
 
 #[cfg(test)]
 mod test {
@@ -20,11 +32,8 @@ mod test {
     where
         Client<S>: ClientHandler<S>,
     {
-        let client = Client::<S>::default();
-        match client.query(&S::Params::default()) {
-            Ok(_) => (return true),
-            Err(_) => (return false),
-        };
+        let client = crate::transport::test_client::<S>();
+        client.query(&S::Params::default()).is_ok()
     }
 
     macro_rules! test_spec {
@@ -43,4 +52,5 @@ mod test {
     test_spec!(Rbe, rbe);
     test_spec!(Hss, hss);
     test_spec!(Wsa, wsa);
+    test_spec!(Ips, ips);
 }
@@ -0,0 +1,426 @@
+use super::activity::ActivityId;
+use super::exports::{Flr, Gst, Hss, Ips, Mpc, Rbe, Sep};
+use super::models::*;
+use super::{cme::Cme, ips::IpsParams};
+use crate::{error::Result, params::ParamsCommon, prelude::*};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Any DONKI record that carries an activity ID and `linkedEvents`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum DonkiEvent {
+    Flare(FlareEvent),
+    Cme(CmeEvent),
+    Shock(InterplanetaryShock),
+    Storm(GeomagneticStorm),
+    Sep(SepEvent),
+    Magnetopause(MagnetopauseCrossing),
+    RadiationBelt(RadiationBeltEnhancement),
+    HighSpeedStream(HighSpeedStream),
+}
+
+impl DonkiEvent {
+    pub fn activity_id(&self) -> ActivityId {
+        match self {
+            Self::Flare(event) => event.flr_id,
+            Self::Cme(event) => event.activity_id,
+            Self::Shock(event) => event.activity_id,
+            Self::Storm(event) => event.gst_id,
+            Self::Sep(event) => event.sep_id,
+            Self::Magnetopause(event) => event.mpc_id,
+            Self::RadiationBelt(event) => event.rbe_id,
+            Self::HighSpeedStream(event) => event.hss_id,
+        }
+    }
+
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::Flare(_) => EventKind::Flr,
+            Self::Cme(_) => EventKind::Cme,
+            Self::Shock(_) => EventKind::Ips,
+            Self::Storm(_) => EventKind::Gst,
+            Self::Sep(_) => EventKind::Sep,
+            Self::Magnetopause(_) => EventKind::Mpc,
+            Self::RadiationBelt(_) => EventKind::Rbe,
+            Self::HighSpeedStream(_) => EventKind::Hss,
+        }
+    }
+
+    /// When the event began
+    pub fn time(&self) -> DateTime<Utc> {
+        match self {
+            Self::Flare(event) => event.begin_time,
+            Self::Cme(event) => event.start_time,
+            Self::Shock(event) => event.event_time,
+            Self::Storm(event) => event.start_time,
+            Self::Sep(event) => event.event_time,
+            Self::Magnetopause(event) => event.event_time,
+            Self::RadiationBelt(event) => event.event_time,
+            Self::HighSpeedStream(event) => event.event_time,
+        }
+    }
+
+    pub fn linked_events(&self) -> &[LinkedEvent] {
+        match self {
+            Self::Flare(event) => &event.linked_events,
+            Self::Cme(event) => &event.linked_events,
+            Self::Shock(event) => &event.linked_events,
+            Self::Storm(event) => &event.linked_events,
+            Self::Sep(event) => &event.linked_events,
+            Self::Magnetopause(event) => &event.linked_events,
+            Self::RadiationBelt(event) => &event.linked_events,
+            Self::HighSpeedStream(event) => &event.linked_events,
+        }
+    }
+
+    pub fn link(&self) -> Option<&str> {
+        match self {
+            Self::Flare(event) => event.link.as_deref(),
+            Self::Cme(event) => event.link.as_deref(),
+            Self::Shock(event) => event.link.as_deref(),
+            Self::Storm(event) => event.link.as_deref(),
+            Self::Sep(event) => event.link.as_deref(),
+            Self::Magnetopause(event) => event.link.as_deref(),
+            Self::RadiationBelt(event) => event.link.as_deref(),
+            Self::HighSpeedStream(event) => event.link.as_deref(),
+        }
+    }
+
+    /// Whether `self` comes before `other` in the chain of effects: by event type
+    /// (flare, CME, shock, storm, ...), then by time
+    fn precedes(&self, other: &DonkiEvent) -> bool {
+        (self.kind().stage(), self.time(), self.activity_id())
+            < (other.kind().stage(), other.time(), other.activity_id())
+    }
+}
+
+/// DONKI events indexed by activity ID, with `linkedEvents` resolved into
+/// cause → effect edges
+///
+/// DONKI links are undirected (a CME and its flare usually list each other);
+/// edges are oriented along the usual chain flare → CME → shock → storm,
+/// falling back to time order between events at the same stage
+#[derive(Debug, Clone, Default)]
+pub struct EventGraph {
+    events: BTreeMap<ActivityId, DonkiEvent>,
+    effects: BTreeMap<ActivityId, BTreeSet<ActivityId>>,
+    causes: BTreeMap<ActivityId, BTreeSet<ActivityId>>,
+    unresolved: BTreeSet<(ActivityId, ActivityId)>,
+}
+
+impl EventGraph {
+    /// Fetch FLR, CME, IPS, GST, SEP, MPC, RBE and HSS events for the
+    /// date range in `params`, with the settings and transport of `client`
+    pub fn fetch<S: Spec>(client: &Client<S>, params: &ParamsCommon) -> Result<Self> {
+        let ips_params = IpsParams::from_common(params);
+        let mut events = Vec::new();
+        events.extend(fetch::<_, Flr>(client, params, DonkiEvent::Flare)?);
+        events.extend(fetch::<_, Cme>(client, params, DonkiEvent::Cme)?);
+        events.extend(fetch::<_, Ips>(client, &ips_params, DonkiEvent::Shock)?);
+        events.extend(fetch::<_, Gst>(client, params, DonkiEvent::Storm)?);
+        events.extend(fetch::<_, Sep>(client, params, DonkiEvent::Sep)?);
+        events.extend(fetch::<_, Mpc>(client, params, DonkiEvent::Magnetopause)?);
+        events.extend(fetch::<_, Rbe>(client, params, DonkiEvent::RadiationBelt)?);
+        events.extend(fetch::<_, Hss>(
+            client,
+            params,
+            DonkiEvent::HighSpeedStream,
+        )?);
+        Ok(Self::from_events(events))
+    }
+
+    /// Index already fetched events and resolve their links
+    pub fn from_events(events: impl IntoIterator<Item = DonkiEvent>) -> Self {
+        let mut graph = Self::default();
+        for event in events {
+            graph.events.insert(event.activity_id(), event);
+        }
+        for (&id, event) in &graph.events {
+            for linked in event.linked_events() {
+                let other_id = linked.activity_id;
+                let Some(other) = graph.events.get(&other_id) else {
+                    graph.unresolved.insert((id, other_id));
+                    continue;
+                };
+                let (cause, effect) = if event.precedes(other) {
+                    (id, other_id)
+                } else {
+                    (other_id, id)
+                };
+                graph.effects.entry(cause).or_default().insert(effect);
+                graph.causes.entry(effect).or_default().insert(cause);
+            }
+        }
+        graph
+    }
+
+    pub fn get(&self, activity_id: ActivityId) -> Option<&DonkiEvent> {
+        self.events.get(&activity_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Every event, ordered by activity ID (and so by time)
+    pub fn events(&self) -> impl Iterator<Item = &DonkiEvent> {
+        self.events.values()
+    }
+
+    /// Every cause → effect edge
+    pub fn edges(&self) -> impl Iterator<Item = (ActivityId, ActivityId)> + '_ {
+        self.effects
+            .iter()
+            .flat_map(|(&cause, effects)| effects.iter().map(move |&effect| (cause, effect)))
+    }
+
+    /// Links to events outside the fetched range, as `(from, to)` activity IDs
+    pub fn unresolved_links(&self) -> impl Iterator<Item = (ActivityId, ActivityId)> + '_ {
+        self.unresolved.iter().copied()
+    }
+
+    /// Events directly caused by `activity_id`
+    pub fn effects(&self, activity_id: ActivityId) -> impl Iterator<Item = &DonkiEvent> {
+        self.neighbours(&self.effects, activity_id)
+    }
+
+    /// Events that directly caused `activity_id`
+    pub fn causes(&self, activity_id: ActivityId) -> impl Iterator<Item = &DonkiEvent> {
+        self.neighbours(&self.causes, activity_id)
+    }
+
+    /// Events with no cause in the graph, e.g. the flares that start each chain
+    pub fn roots(&self) -> impl Iterator<Item = &DonkiEvent> {
+        self.events
+            .iter()
+            .filter(|(id, _)| !self.causes.contains_key(id))
+            .map(|(_, event)| event)
+    }
+
+    /// Everything downstream of `activity_id`, nearest first
+    pub fn downstream(&self, activity_id: ActivityId) -> Vec<&DonkiEvent> {
+        self.walk(&self.effects, activity_id)
+    }
+
+    /// Everything upstream of `activity_id`, nearest first
+    pub fn upstream(&self, activity_id: ActivityId) -> Vec<&DonkiEvent> {
+        self.walk(&self.causes, activity_id)
+    }
+
+    /// Every maximal cause → effect chain starting at `activity_id`,
+    /// e.g. `[FLR, CME, IPS, GST, RBE]`
+    pub fn chains(&self, activity_id: ActivityId) -> Vec<Vec<&DonkiEvent>> {
+        let Some(start) = self.events.get(&activity_id) else {
+            return Vec::new();
+        };
+        let mut chains = Vec::new();
+        let mut stack = vec![vec![start]];
+        while let Some(chain) = stack.pop() {
+            let last = chain[chain.len() - 1].activity_id();
+            let next: Vec<_> = self.effects(last).collect();
+            if next.is_empty() {
+                chains.push(chain);
+                continue;
+            }
+            // effects always sit at a later stage or time, so chains cannot cycle
+            for event in next.into_iter().rev() {
+                let mut longer = chain.clone();
+                longer.push(event);
+                stack.push(longer);
+            }
+        }
+        chains
+    }
+
+    /// Nodes and edges, ready to serialize (e.g. to JSON)
+    pub fn export(&self) -> GraphExport<'_> {
+        GraphExport {
+            nodes: self
+                .events
+                .values()
+                .map(|event| GraphNode {
+                    id: event.activity_id(),
+                    kind: event.kind(),
+                    time: event.time(),
+                    link: event.link(),
+                })
+                .collect(),
+            edges: self
+                .edges()
+                .map(|(cause, effect)| GraphEdge { cause, effect })
+                .collect(),
+        }
+    }
+
+    /// Graphviz DOT source, one node per event and one arrow per edge
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph donki {\n    rankdir=LR;\n");
+        for event in self.events.values() {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"];\n",
+                event.activity_id(),
+                event.kind(),
+                event.time().format("%Y-%m-%d %H:%M")
+            ));
+        }
+        for (cause, effect) in self.edges() {
+            dot.push_str(&format!("    \"{cause}\" -> \"{effect}\";\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        edges: &'a BTreeMap<ActivityId, BTreeSet<ActivityId>>,
+        activity_id: ActivityId,
+    ) -> impl Iterator<Item = &'a DonkiEvent> {
+        edges
+            .get(&activity_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.events.get(id))
+    }
+
+    fn walk(
+        &self,
+        edges: &BTreeMap<ActivityId, BTreeSet<ActivityId>>,
+        activity_id: ActivityId,
+    ) -> Vec<&DonkiEvent> {
+        let mut seen = BTreeSet::from([activity_id]);
+        let mut queue = VecDeque::from([activity_id]);
+        let mut found = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            for &next in edges.get(&id).into_iter().flatten() {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                    found.extend(self.events.get(&next));
+                }
+            }
+        }
+        found
+    }
+}
+
+/// Serializable snapshot of an [EventGraph]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphExport<'a> {
+    pub nodes: Vec<GraphNode<'a>>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphNode<'a> {
+    pub id: ActivityId,
+    pub kind: EventKind,
+    pub time: DateTime<Utc>,
+    pub link: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphEdge {
+    pub cause: ActivityId,
+    pub effect: ActivityId,
+}
+
+fn fetch<S, T>(
+    client: &Client<S>,
+    params: &T::Params,
+    wrap: impl Fn(<T::ResponseType as IntoIterator>::Item) -> DonkiEvent,
+) -> Result<Vec<DonkiEvent>>
+where
+    S: Spec,
+    T: Spec,
+    T::ResponseType: IntoIterator,
+    Client<T>: ClientHandler<T>,
+{
+    let events = client.with_spec::<T>().query(params)?;
+    Ok(events.into_iter().map(wrap).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FLR: &str = "2024-05-08T04:37:00-FLR-001";
+    const CME: &str = "2024-05-08T05:36:00-CME-001";
+    const IPS: &str = "2024-05-10T16:36:00-IPS-001";
+    const GST: &str = "2024-05-10T15:00:00-GST-001";
+
+    fn id(id: &str) -> ActivityId {
+        id.parse().unwrap()
+    }
+
+    fn graph() -> EventGraph {
+        let client = crate::transport::test_client::<Cme>();
+        EventGraph::fetch(&client, &ParamsCommon::default()).unwrap()
+    }
+
+    fn ids<'a>(events: impl IntoIterator<Item = &'a DonkiEvent>) -> Vec<String> {
+        events
+            .into_iter()
+            .map(|event| event.activity_id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn links_flare_to_storm() {
+        let graph = graph();
+        assert_eq!(graph.len(), 10);
+        assert_eq!(graph.get(id(CME)).unwrap().kind(), EventKind::Cme);
+
+        assert_eq!(ids(graph.effects(id(FLR))), vec![CME]);
+        assert_eq!(ids(graph.causes(id(CME))), vec![FLR]);
+        // the storm's Kp window opens before the shock, but the shock still comes first
+        assert_eq!(ids(graph.causes(id(GST))), vec![CME, IPS]);
+
+        let chains: Vec<Vec<_>> = graph.chains(id(FLR)).into_iter().map(ids).collect();
+        assert!(chains
+            .iter()
+            .any(|chain| chain == &[FLR, CME, IPS, GST, "2024-05-11T18:20:00-RBE-001"]));
+        assert!(chains
+            .iter()
+            .any(|chain| chain == &[FLR, CME, IPS, "2024-05-10T17:35:00-MPC-001"]));
+
+        let downstream = ids(graph.downstream(id(FLR)));
+        assert_eq!(&downstream[..1], &[CME]);
+        assert_eq!(downstream.len(), 5);
+        assert_eq!(ids(graph.upstream(id(GST))), vec![CME, IPS, FLR]);
+
+        let roots = ids(graph.roots());
+        assert!(roots.iter().any(|root| root == FLR));
+        assert!(roots
+            .iter()
+            .any(|root| root == "2024-05-10T06:27:00-FLR-001"));
+        assert!(!roots.iter().any(|root| root == CME));
+        assert_eq!(graph.unresolved_links().count(), 0);
+    }
+
+    #[test]
+    fn exports_nodes_and_edges() {
+        let graph = graph();
+        let export = serde_json::to_value(graph.export()).unwrap();
+        assert_eq!(export["nodes"].as_array().unwrap().len(), 10);
+        assert!(export["edges"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!({"cause": FLR, "effect": CME})));
+        assert!(graph.to_dot().contains(&format!("\"{FLR}\" -> \"{CME}\";")));
+    }
+
+    #[test]
+    fn keeps_links_outside_the_range() {
+        let client = crate::transport::test_client::<Cme>();
+        let cmes = client.query(&ParamsCommon::default()).unwrap();
+        let graph = EventGraph::from_events(cmes.into_iter().map(DonkiEvent::Cme));
+        assert_eq!(graph.edges().count(), 0);
+        assert_eq!(graph.unresolved_links().count(), 3);
+        assert_eq!(graph.roots().count(), 2);
+    }
+}
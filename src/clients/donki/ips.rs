@@ -1,56 +1,144 @@
 #![allow(dead_code)]
 pub use super::exports::Ips;
+use crate::query::QueryValues;
+use chrono::NaiveDate;
 
-#[derive(Debug, Clone, serde::Serialize)]
-#[allow(non_camel_case_types)]
-pub enum Location {
-    Earth,
-    MESSENGER,
-    STEREO_A,
-    STEREO_B,
+crate::query_enum! {
+    /// Where a shock was detected
+    #[allow(non_camel_case_types)]
+    pub enum Location("location") {
+        ALL = "ALL",
+        Earth = "Earth",
+        MESSENGER = "MESSENGER",
+        STEREO_A = "STEREO A",
+        STEREO_B = "STEREO B",
+    }
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
-#[allow(non_camel_case_types)]
-pub enum Catalog {
-    SWRC_CATALOG,
-    WINSLOW_MESSENGER_ICME_CATALOG,
+crate::query_enum! {
+    /// Catalog a shock is listed in
+    #[allow(non_camel_case_types)]
+    pub enum Catalog("catalog") {
+        ALL = "ALL",
+        SWRC_CATALOG = "SWRC_CATALOG",
+        WINSLOW_MESSENGER_ICME_CATALOG = "WINSLOW_MESSENGER_ICME_CATALOG",
+    }
 }
 
-#[derive(Debug, Default, Clone, serde::Serialize)]
+/// Query parameters for the DONKI IPS API
+///
+/// Without dates the API searches the last
+/// [DONKI_DEFAULT_WINDOW_DAYS](crate::params::DONKI_DEFAULT_WINDOW_DAYS) days
+#[derive(Debug, Default, Clone, serde::Serialize, QueryValues)]
 #[serde(rename_all = "camelCase")]
 pub struct IpsParams {
     /// Default: 30 days prior to current UTC date
-    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<NaiveDate>,
     /// Default: current UTC date
-    end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<NaiveDate>,
     /// Default: ALL
+    #[serde(skip_serializing_if = "Option::is_none")]
     location: Option<Location>,
     /// Default: ALL
+    #[serde(skip_serializing_if = "Option::is_none")]
     catalog: Option<Catalog>,
 }
 
-impl crate::query::QueryValues for IpsParams {
-    fn values(&self) -> std::collections::HashMap<String, String> {
-        let mut values = std::collections::HashMap::new();
-        if let Some(start_date) = &self.start_date {
-            values.insert("startDate".to_string(), start_date.to_string());
+impl IpsParams {
+    /// Shocks from `start` to `end`, inclusive
+    pub fn range(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            end_date: Some(end),
+            ..Default::default()
+        }
+    }
+
+    /// Shocks from `start` to today
+    pub fn since(start: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            ..Default::default()
         }
-        if let Some(end_date) = &self.end_date {
-            values.insert("endDate".to_string(), end_date.to_string());
+    }
+
+    /// Only shocks detected at `location`
+    pub fn location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Only shocks listed in `catalog`
+    pub fn catalog(mut self, catalog: Catalog) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// The same date range as `params`, at every location and in every catalog
+    pub(crate) fn from_common(params: &crate::params::ParamsCommon) -> Self {
+        Self {
+            start_date: params.start_date(),
+            end_date: params.end_date(),
+            ..Default::default()
         }
-        if let Some(location) = &self.location {
-            values.insert(
-                "location".to_string(),
-                serde_json::to_string(location).unwrap(),
-            );
+    }
+
+    /// The dates the API will search, with defaults filled in
+    pub fn window(&self) -> (NaiveDate, NaiveDate) {
+        crate::params::donki_window(self.start_date, self.end_date)
+    }
+
+    /// Reject a range that ends before it starts
+    pub fn validate(&self) -> crate::error::Result<()> {
+        crate::params::validate_donki_window(self.start_date, self.end_date)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{prelude::*, query::QueryValues};
+
+    #[test]
+    fn enums_round_trip() {
+        for location in Location::VARIANTS {
+            assert_eq!(location.as_str().parse::<Location>().unwrap(), *location);
+            let json = serde_json::to_string(location).unwrap();
+            assert_eq!(json, format!("{:?}", location.as_str()));
+            assert_eq!(serde_json::from_str::<Location>(&json).unwrap(), *location);
         }
-        if let Some(catalog) = &self.catalog {
-            values.insert(
-                "catalog".to_string(),
-                serde_json::to_string(catalog).unwrap(),
-            );
+        for catalog in Catalog::VARIANTS {
+            assert_eq!(catalog.to_string().parse::<Catalog>().unwrap(), *catalog);
         }
-        values
+        assert_eq!(Location::STEREO_A.to_string(), "STEREO A");
+        assert!(matches!(
+            "STEREO_A".parse::<Location>(),
+            Err(crate::error::Error::InvalidParameter {
+                name: "location",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn query_and_query_with_agree() {
+        let ymd = |day| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let params = IpsParams::range(ymd(8), ymd(12))
+            .location(Location::STEREO_A)
+            .catalog(Catalog::WINSLOW_MESSENGER_ICME_CATALOG);
+        assert_eq!(params.values().unwrap()["location"], "STEREO A");
+
+        let (client, urls) = crate::transport::recording_client::<Ips>();
+        client.query(&params).unwrap();
+        client.query_with(params.clone()).unwrap();
+        let urls = urls.lock().unwrap();
+        assert_eq!(
+            urls[0],
+            "https://api.nasa.gov/DONKI/IPS?catalog=WINSLOW_MESSENGER_ICME_CATALOG\
+             &endDate=2024-05-12&location=STEREO%20A&startDate=2024-05-08&api_key=test-key"
+        );
+        assert_eq!(urls[0], urls[1]);
     }
 }
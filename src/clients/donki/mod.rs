@@ -1,7 +1,15 @@
+/// Parsed activity identifiers
+pub mod activity;
 /// Coronal Mass Ejection
 pub mod cme;
 pub mod endpoints;
 pub mod exports;
+/// Linked events across every DONKI event type
+pub mod graph;
+/// Typed DONKI records
+pub mod models;
+/// Space weather alerts and reports
+pub mod notifications;
 /// Geomagnetic Storm
 pub mod gst {
     pub use super::exports::Gst;
@@ -0,0 +1,649 @@
+use super::activity::ActivityId;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An instrument that observed an event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Instrument {
+    /// e.g. `SOHO: LASCO/C2`
+    pub display_name: String,
+}
+
+/// Another DONKI event this one is linked to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedEvent {
+    #[serde(rename = "activityID")]
+    pub activity_id: ActivityId,
+}
+
+/// The DONKI event types that carry activity IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    #[serde(rename = "FLR")]
+    Flr,
+    #[serde(rename = "CME")]
+    Cme,
+    #[serde(rename = "IPS")]
+    Ips,
+    #[serde(rename = "GST")]
+    Gst,
+    #[serde(rename = "SEP")]
+    Sep,
+    #[serde(rename = "MPC")]
+    Mpc,
+    #[serde(rename = "RBE")]
+    Rbe,
+    #[serde(rename = "HSS")]
+    Hss,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 8] = [
+        Self::Flr,
+        Self::Cme,
+        Self::Ips,
+        Self::Gst,
+        Self::Sep,
+        Self::Mpc,
+        Self::Rbe,
+        Self::Hss,
+    ];
+
+    /// The code used in activity IDs and endpoint names, e.g. `FLR`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Flr => "FLR",
+            Self::Cme => "CME",
+            Self::Ips => "IPS",
+            Self::Gst => "GST",
+            Self::Sep => "SEP",
+            Self::Mpc => "MPC",
+            Self::Rbe => "RBE",
+            Self::Hss => "HSS",
+        }
+    }
+
+    /// Position in the usual Sun-to-Earth chain of effects; links point from
+    /// lower to higher stages
+    pub(crate) fn stage(&self) -> u8 {
+        match self {
+            Self::Flr => 0,
+            Self::Cme | Self::Hss => 1,
+            Self::Ips | Self::Sep => 2,
+            Self::Gst => 3,
+            Self::Mpc | Self::Rbe => 4,
+        }
+    }
+}
+
+impl std::str::FromStr for EventKind {
+    type Err = crate::error::Error;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.code() == code)
+            .ok_or_else(|| crate::error::Error::InvalidParameter {
+                name: "eventType",
+                reason: format!("unknown DONKI event type {code:?}"),
+            })
+    }
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// A Coronal Mass Ejection, with every analysis submitted for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CmeEvent {
+    #[serde(rename = "activityID")]
+    pub activity_id: ActivityId,
+    pub catalog: String,
+    #[serde(with = "time")]
+    pub start_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub source_location: Option<String>,
+    #[serde(default)]
+    pub active_region_num: Option<u32>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub note: Option<String>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub cme_analyses: Vec<CmeEventAnalysis>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+impl CmeEvent {
+    /// The analysis flagged as most accurate, if any
+    pub fn most_accurate_analysis(&self) -> Option<&CmeEventAnalysis> {
+        self.cme_analyses
+            .iter()
+            .find(|analysis| analysis.is_most_accurate)
+    }
+}
+
+/// One analysis of a CME's speed and direction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CmeEventAnalysis {
+    #[serde(default)]
+    pub is_most_accurate: bool,
+    /// When the CME front reaches 21.5 solar radii
+    #[serde(rename = "time21_5", default, with = "time::option")]
+    pub time_21_5: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    #[serde(default)]
+    pub half_angle: Option<f64>,
+    /// km/s
+    #[serde(default)]
+    pub speed: Option<f64>,
+    /// SWPC speed class: `S`, `C`, `O`, `R` or `ER`
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub feature_code: Option<String>,
+    #[serde(default)]
+    pub measurement_technique: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub level_of_data: Option<u8>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub link: Option<String>,
+    /// WSA-ENLIL model runs driven by this analysis
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub enlil_list: Vec<EnlilRun>,
+}
+
+/// A WSA-ENLIL model run, as attached to a CME analysis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnlilRun {
+    #[serde(with = "time")]
+    pub model_completion_time: DateTime<Utc>,
+    /// Outer boundary of the run in AU
+    #[serde(default)]
+    pub au: Option<f64>,
+    #[serde(default, with = "time::option")]
+    pub estimated_shock_arrival_time: Option<DateTime<Utc>>,
+    /// Hours
+    #[serde(default)]
+    pub estimated_duration: Option<f64>,
+    #[serde(rename = "rmin_re", default)]
+    pub rmin_re: Option<f64>,
+    #[serde(rename = "kp_18", default)]
+    pub kp_18: Option<u8>,
+    #[serde(rename = "kp_90", default)]
+    pub kp_90: Option<u8>,
+    #[serde(rename = "kp_135", default)]
+    pub kp_135: Option<u8>,
+    #[serde(rename = "kp_180", default)]
+    pub kp_180: Option<u8>,
+    #[serde(rename = "isEarthGB", default)]
+    pub is_earth_glancing_blow: bool,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub impact_list: Vec<Impact>,
+    #[serde(rename = "cmeIDs", default, deserialize_with = "null_as_empty")]
+    pub cme_ids: Vec<ActivityId>,
+}
+
+/// A predicted arrival at a spacecraft or planet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Impact {
+    pub is_glancing_blow: bool,
+    /// e.g. `Earth`, `STEREO A`
+    pub location: String,
+    #[serde(with = "time")]
+    pub arrival_time: DateTime<Utc>,
+}
+
+/// A solar flare
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlareEvent {
+    #[serde(rename = "flrID")]
+    pub flr_id: ActivityId,
+    #[serde(default)]
+    pub catalog: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(with = "time")]
+    pub begin_time: DateTime<Utc>,
+    #[serde(default, with = "time::option")]
+    pub peak_time: Option<DateTime<Utc>>,
+    #[serde(default, with = "time::option")]
+    pub end_time: Option<DateTime<Utc>>,
+    /// GOES X-ray class, e.g. `X1.0`
+    pub class_type: String,
+    /// Heliographic location, e.g. `S22W11`
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub source_location: Option<String>,
+    #[serde(default)]
+    pub active_region_num: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub note: Option<String>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+/// A geomagnetic storm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeomagneticStorm {
+    #[serde(rename = "gstID")]
+    pub gst_id: ActivityId,
+    #[serde(with = "time")]
+    pub start_time: DateTime<Utc>,
+    /// Kp observations over the storm, in time order
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub all_kp_index: Vec<KpIndex>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+impl GeomagneticStorm {
+    /// The strongest Kp observed
+    pub fn max_kp(&self) -> Option<f64> {
+        self.all_kp_index
+            .iter()
+            .map(|kp| kp.kp_index)
+            .max_by(f64::total_cmp)
+    }
+}
+
+/// One Kp observation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KpIndex {
+    #[serde(with = "time")]
+    pub observed_time: DateTime<Utc>,
+    /// 0 to 9
+    pub kp_index: f64,
+    /// e.g. `NOAA`
+    pub source: String,
+}
+
+/// A solar energetic particle event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SepEvent {
+    #[serde(rename = "sepID")]
+    pub sep_id: ActivityId,
+    #[serde(with = "time")]
+    pub event_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+/// A magnetopause crossing at geosynchronous orbit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MagnetopauseCrossing {
+    #[serde(rename = "mpcID")]
+    pub mpc_id: ActivityId,
+    #[serde(with = "time")]
+    pub event_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+/// A radiation belt enhancement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadiationBeltEnhancement {
+    #[serde(rename = "rbeID")]
+    pub rbe_id: ActivityId,
+    #[serde(with = "time")]
+    pub event_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+/// A high speed solar wind stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighSpeedStream {
+    #[serde(rename = "hssID")]
+    pub hss_id: ActivityId,
+    #[serde(with = "time")]
+    pub event_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+/// An interplanetary shock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterplanetaryShock {
+    #[serde(rename = "activityID")]
+    pub activity_id: ActivityId,
+    #[serde(default)]
+    pub catalog: Option<String>,
+    /// Where the shock was observed, e.g. `Earth`, `STEREO A`
+    pub location: String,
+    #[serde(with = "time")]
+    pub event_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, with = "time::option")]
+    pub submission_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub version_id: Option<u32>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+}
+
+/// A WSA-ENLIL solar wind simulation
+///
+/// Simulations are not events: they are tied to the CMEs they model through
+/// `cme_ids` rather than `linkedEvents`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsaEnlilSimulation {
+    /// e.g. `WSA-ENLIL/30731/1`
+    #[serde(rename = "simulationID")]
+    pub simulation_id: String,
+    #[serde(with = "time")]
+    pub model_completion_time: DateTime<Utc>,
+    /// Outer boundary of the run in AU
+    #[serde(default)]
+    pub au: Option<f64>,
+    #[serde(default, with = "time::option")]
+    pub estimated_shock_arrival_time: Option<DateTime<Utc>>,
+    /// Hours
+    #[serde(default)]
+    pub estimated_duration: Option<f64>,
+    #[serde(rename = "rmin_re", default)]
+    pub rmin_re: Option<f64>,
+    #[serde(rename = "kp_18", default)]
+    pub kp_18: Option<u8>,
+    #[serde(rename = "kp_90", default)]
+    pub kp_90: Option<u8>,
+    #[serde(rename = "kp_135", default)]
+    pub kp_135: Option<u8>,
+    #[serde(rename = "kp_180", default)]
+    pub kp_180: Option<u8>,
+    #[serde(rename = "isEarthGB", default)]
+    pub is_earth_glancing_blow: bool,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub impact_list: Vec<Impact>,
+    #[serde(rename = "cmeIDs", default, deserialize_with = "null_as_empty")]
+    pub cme_ids: Vec<ActivityId>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub cme_inputs: Vec<CmeInput>,
+    #[serde(default)]
+    pub link: Option<String>,
+}
+
+/// The CME analysis a simulation was driven by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CmeInput {
+    #[serde(rename = "cmeid")]
+    pub cme_id: ActivityId,
+    #[serde(with = "time")]
+    pub cme_start_time: DateTime<Utc>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// km/s
+    #[serde(default)]
+    pub speed: Option<f64>,
+    #[serde(default)]
+    pub half_angle: Option<f64>,
+    #[serde(rename = "time21_5", default, with = "time::option")]
+    pub time_21_5: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub is_most_accurate: bool,
+    #[serde(default)]
+    pub level_of_data: Option<u8>,
+}
+
+/// DONKI sends absent lists as `null`
+pub(crate) fn null_as_empty<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// DONKI sends absent text as `null` or `""`
+pub(crate) fn empty_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.filter(|s| !s.trim().is_empty()))
+}
+
+/// DONKI timestamps: `2024-05-08T05:36Z`, occasionally with seconds
+pub(crate) mod time {
+    use super::*;
+    use chrono::Timelike;
+
+    const FORMAT: &str = "%Y-%m-%dT%H:%MZ";
+    /// For times the minute-precision format would truncate
+    const FORMAT_SECONDS: &str = "%Y-%m-%dT%H:%M:%S%.fZ";
+
+    pub(crate) fn parse(value: &str) -> Option<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(value, FORMAT)
+            .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%SZ"))
+            .map(|time| time.and_utc())
+            .ok()
+            .or_else(|| {
+                DateTime::parse_from_rfc3339(value)
+                    .ok()
+                    .map(|time| time.with_timezone(&Utc))
+            })
+    }
+
+    /// DONKI's own minute precision, with seconds only when there are any
+    pub(crate) fn serialize<S: Serializer>(
+        time: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let format = if time.second() == 0 && time.nanosecond() == 0 {
+            FORMAT
+        } else {
+            FORMAT_SECONDS
+        };
+        serializer.collect_str(&time.format(format))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid DONKI time {value:?}")))
+    }
+
+    pub(crate) mod option {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(
+            time: &Option<DateTime<Utc>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match time {
+                Some(time) => super::serialize(time, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<DateTime<Utc>>, D::Error> {
+            match Option::<String>::deserialize(deserializer)? {
+                Some(value) if !value.is_empty() => parse(&value).map(Some).ok_or_else(|| {
+                    serde::de::Error::custom(format!("invalid DONKI time {value:?}"))
+                }),
+                _ => Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::donki::exports::*;
+    use crate::params::ParamsCommon;
+    use crate::prelude::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, day, hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn times_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Timed(#[serde(with = "time")] DateTime<Utc>);
+
+        let minutes = Timed(at(8, 5, 9));
+        let seconds = Timed(Utc.with_ymd_and_hms(2024, 5, 8, 5, 9, 30).unwrap());
+        let fraction = Timed(seconds.0 + chrono::Duration::milliseconds(250));
+        for (timed, expected) in [
+            (minutes, "2024-05-08T05:09Z"),
+            (seconds, "2024-05-08T05:09:30Z"),
+            (fraction, "2024-05-08T05:09:30.250Z"),
+        ] {
+            let json = serde_json::to_value(&timed).unwrap();
+            assert_eq!(json, expected);
+            assert_eq!(serde_json::from_value::<Timed>(json).unwrap(), timed);
+        }
+    }
+
+    #[test]
+    fn decodes_every_event_type() {
+        let params = ParamsCommon::default();
+        let flares = crate::transport::test_client::<Flr>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(flares[0].class_type, "X1.0");
+        assert_eq!(flares[0].peak_time, Some(at(8, 5, 9)));
+        assert_eq!(flares[0].source_location.as_deref(), Some("S22W11"));
+        assert_eq!(flares[0].active_region_num, Some(13664));
+        assert!(flares[1].linked_events.is_empty());
+
+        let storms = crate::transport::test_client::<Gst>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(storms[0].all_kp_index.len(), 3);
+        assert_eq!(storms[0].max_kp(), Some(9.0));
+
+        let sep = crate::transport::test_client::<Sep>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(sep[0].linked_events[0].activity_id, flares[1].flr_id);
+        let mpc = crate::transport::test_client::<Mpc>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(mpc[0].event_time, at(10, 17, 35));
+        let rbe = crate::transport::test_client::<Rbe>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(rbe[0].instruments[0].display_name, "GOES-P: SEISS 2.0 MeV");
+        let hss = crate::transport::test_client::<Hss>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(hss[0].instruments.len(), 2);
+
+        let ips = crate::transport::test_client::<Ips>()
+            .query(&Default::default())
+            .unwrap();
+        assert_eq!(ips[0].location, "Earth");
+        assert_eq!(ips[0].linked_events.len(), 3);
+
+        let wsa = crate::transport::test_client::<Wsa>()
+            .query(&params)
+            .unwrap();
+        assert_eq!(wsa[0].impact_list.len(), 2);
+        assert!(wsa[0].impact_list[1].is_glancing_blow);
+        assert_eq!(wsa[0].cme_inputs[0].cme_id, wsa[0].cme_ids[0]);
+        assert_eq!(wsa[0].cme_ids[0].kind(), EventKind::Cme);
+        assert_eq!(wsa[0].kp_180, Some(9));
+    }
+
+    #[test]
+    fn round_trips_donki_times() {
+        let json = r#"{"observedTime":"2024-05-10T18:00Z","kpIndex":8.0,"source":"NOAA"}"#;
+        let kp: KpIndex = serde_json::from_str(json).unwrap();
+        assert_eq!(kp.observed_time, at(10, 18, 0));
+        assert_eq!(serde_json::to_string(&kp).unwrap(), json);
+        assert_eq!(
+            time::parse("2024-05-10T18:00:30Z"),
+            Some(at(10, 18, 0) + chrono::Duration::seconds(30))
+        );
+        assert!(time::parse("yesterday").is_none());
+    }
+}
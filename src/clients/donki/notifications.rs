@@ -0,0 +1,184 @@
+use super::activity::ActivityId;
+use super::endpoints::NOTIFICATIONS_BASE_URL;
+use super::models::time;
+use crate::{prelude::*, query::QueryValues};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+crate::query_enum! {
+    /// Which notifications to return
+    pub enum NotificationType("type") {
+        All = "all",
+        Flr = "FLR",
+        Sep = "SEP",
+        Cme = "CME",
+        Ips = "IPS",
+        Mpc = "MPC",
+        Gst = "GST",
+        Rbe = "RBE",
+        /// Weekly summaries
+        Report = "report",
+    }
+}
+
+/// The type of a notification record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MessageType {
+    #[serde(rename = "FLR")]
+    Flr,
+    #[serde(rename = "SEP")]
+    Sep,
+    #[serde(rename = "CME")]
+    Cme,
+    #[serde(rename = "IPS")]
+    Ips,
+    #[serde(rename = "MPC")]
+    Mpc,
+    #[serde(rename = "GST")]
+    Gst,
+    #[serde(rename = "RBE")]
+    Rbe,
+    /// Weekly summaries; records spell it `Report`
+    #[serde(rename = "Report", alias = "report")]
+    Report,
+    /// A type this crate doesn't know yet
+    #[serde(other)]
+    Unknown,
+}
+
+/// Query parameters for the DONKI Notifications API
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, QueryValues)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationsParams {
+    /// default 7 days prior to current UTC date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<NaiveDate>,
+    /// default current UTC date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<NaiveDate>,
+    /// default [NotificationType::All]
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<NotificationType>,
+}
+
+impl NotificationsParams {
+    /// Notifications issued from `start` to `end`, inclusive
+    pub fn range(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            end_date: Some(end),
+            ..Default::default()
+        }
+    }
+
+    /// Only notifications of one type
+    pub fn kind(mut self, kind: NotificationType) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+}
+
+/// A notification (alert or weekly report) sent by the Space Weather Research Center
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    /// e.g. `20240508-AL-001`
+    #[serde(rename = "messageID")]
+    pub message_id: String,
+    #[serde(rename = "messageType")]
+    pub message_type: MessageType,
+    #[serde(rename = "messageIssueTime", with = "time")]
+    pub message_issue_time: DateTime<Utc>,
+    #[serde(rename = "messageURL")]
+    pub message_url: String,
+    /// The full message text
+    #[serde(rename = "messageBody")]
+    pub message_body: String,
+}
+
+impl Notification {
+    /// Activity IDs of the events the message refers to
+    pub fn activity_ids(&self) -> Vec<ActivityId> {
+        ActivityId::find_all(&self.message_body)
+    }
+}
+
+/// Space weather notifications
+pub struct Notifications;
+
+impl Spec for Notifications {
+    const BASE_URL: &'static str = NOTIFICATIONS_BASE_URL;
+    type Params = NotificationsParams;
+    type ResponseType = Vec<Notification>;
+
+    fn validate(params: &NotificationsParams) -> crate::error::Result<()> {
+        crate::params::validate_donki_window(params.start_date, params.end_date)
+    }
+}
+
+// Implement Client Handling for Notifications
+impl ClientHandler<Notifications> for Client<Notifications> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+    use crate::transport::ymd;
+
+    #[test]
+    fn test_notifications() {
+        let client = crate::transport::test_client::<Notifications>();
+        let notifications = client.query(&NotificationsParams::default()).unwrap();
+        assert_eq!(notifications.len(), 6);
+        assert_eq!(notifications[0].message_id, "20240508-AL-001");
+        assert_eq!(notifications[0].message_type, MessageType::Flr);
+        assert_eq!(
+            notifications[0].message_issue_time.to_rfc3339(),
+            "2024-05-08T05:21:00+00:00"
+        );
+        assert!(notifications[1]
+            .message_body
+            .contains("CME affecting Earth"));
+        assert_eq!(notifications[5].message_type, MessageType::Report);
+    }
+
+    #[test]
+    fn filters_by_type() {
+        let client = crate::transport::test_client::<Notifications>();
+        let params = NotificationsParams::range(ymd(2024, 5, 8), ymd(2024, 5, 12))
+            .kind(NotificationType::Flr);
+        let flares = client.query(&params).unwrap();
+        assert_eq!(flares.len(), 2);
+        assert!(flares
+            .iter()
+            .all(|note| note.message_type == MessageType::Flr));
+
+        // `query_with` sends the same parameters
+        assert_eq!(client.query_with(params.clone()).unwrap(), flares);
+        let reports = Client::<Notifications>::build_query(
+            client.config(),
+            NotificationsParams::default().kind(NotificationType::Report),
+        )
+        .unwrap();
+        assert!(reports.contains("?type=report&"));
+
+        let backwards = NotificationsParams::range(ymd(2024, 5, 12), ymd(2024, 5, 8));
+        assert!(matches!(
+            client.query(&backwards),
+            Err(Error::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn reads_unknown_types() {
+        let read =
+            |kind: &str| serde_json::from_value::<MessageType>(serde_json::json!(kind)).unwrap();
+        assert_eq!(read("Report"), MessageType::Report);
+        assert_eq!(read("report"), MessageType::Report);
+        assert_eq!(read("HSS"), MessageType::Unknown);
+        assert_eq!(
+            "FLR".parse::<NotificationType>().unwrap(),
+            NotificationType::Flr
+        );
+        assert!("HSS".parse::<NotificationType>().is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use super::imagery::{EarthImagery, ParamsEarthImagery};
+use crate::{
+    error::{Error, Result},
+    prelude::*,
+    query::QueryValues,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// The Landsat 8 scene of a location closest to a date
+pub struct EarthAssets;
+
+/// Query parameters for the Earth Assets API
+///
+/// Built with [ParamsEarthAssets::new]; the API requires a date
+#[derive(Debug, Clone, PartialEq, Serialize, QueryValues)]
+pub struct ParamsEarthAssets {
+    /// Latitude
+    pub lat: f64,
+    /// Longitude
+    pub lon: f64,
+    /// Date to search around
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Width and height of the area in degrees
+    pub dim: f64,
+}
+
+impl Default for ParamsEarthAssets {
+    fn default() -> Self {
+        Self {
+            lat: 0.0,
+            lon: 0.0,
+            date: None,
+            dim: ParamsEarthImagery::DEFAULT_DIM,
+        }
+    }
+}
+
+impl ParamsEarthAssets {
+    /// The scene of `lat`, `lon` closest to `date`
+    pub fn new(lat: f64, lon: f64, date: NaiveDate) -> Self {
+        Self {
+            lat,
+            lon,
+            date: Some(date),
+            ..Default::default()
+        }
+    }
+
+    pub fn dim(mut self, dim: f64) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    /// Imagery of the same area, on the date `asset` was captured
+    pub fn imagery(&self, asset: &EarthAsset) -> ParamsEarthImagery {
+        ParamsEarthImagery::new(self.lat, self.lon)
+            .dim(self.dim)
+            .date(asset.date.date())
+    }
+
+    /// Reject coordinates off the globe, empty areas and a missing date
+    pub fn validate(&self) -> Result<()> {
+        super::validate_area(self.lat, self.lon, self.dim)?;
+        if self.date.is_none() {
+            return Err(Error::InvalidParameter {
+                name: "date",
+                reason: "is required".to_owned(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The scene matching an assets query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EarthAsset {
+    /// When the scene was captured (UTC)
+    pub date: NaiveDateTime,
+    /// Landsat scene ID, e.g. `LC08_L1TP_127059_20140204_20170425_01_T1`
+    pub id: String,
+    pub resource: AssetResource,
+    #[serde(default)]
+    pub service_version: Option<String>,
+    /// Thumbnail of the scene
+    pub url: String,
+}
+
+/// Where a scene comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetResource {
+    /// e.g. `LANDSAT/LC08/C01/T1_SR`
+    pub dataset: String,
+    /// e.g. `earth`
+    pub planet: String,
+}
+
+impl Spec for EarthAssets {
+    const BASE_URL: &'static str = "https://api.nasa.gov/planetary/earth/assets";
+    type Params = ParamsEarthAssets;
+    type ResponseType = EarthAsset;
+
+    fn validate(params: &ParamsEarthAssets) -> Result<()> {
+        params.validate()
+    }
+}
+
+// Implement Client Handling for EarthAssets
+impl ClientHandler<EarthAssets> for Client<EarthAssets> {}
+
+impl Client<EarthAssets> {
+    /// Fetch the PNG of `asset`, as found by a query for `params`
+    pub fn imagery(&self, params: &ParamsEarthAssets, asset: &EarthAsset) -> Result<Vec<u8>> {
+        self.with_spec::<EarthImagery>()
+            .query(&params.imagery(asset))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn params() -> ParamsEarthAssets {
+        ParamsEarthAssets::new(1.5, 100.75, NaiveDate::from_ymd_opt(2014, 2, 1).unwrap()).dim(0.1)
+    }
+
+    #[test]
+    fn test_earth_assets() {
+        let client = crate::transport::test_client::<EarthAssets>();
+        let asset = client.query(&params()).unwrap();
+        assert_eq!(asset.id, "LC08_L1TP_127059_20140204_20170425_01_T1");
+        assert_eq!(asset.resource.dataset, "LANDSAT/LC08/C01/T1_SR");
+        assert_eq!(asset.resource.planet, "earth");
+        assert_eq!(
+            asset.date.date(),
+            NaiveDate::from_ymd_opt(2014, 2, 4).unwrap()
+        );
+
+        // the nearest pass, not the date asked for
+        let imagery = params().imagery(&asset);
+        assert_eq!(imagery.date, Some(asset.date.date()));
+        assert_eq!(imagery.dim, 0.1);
+        let png = client.imagery(&params(), &asset).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn requires_a_date() {
+        assert!(matches!(
+            ParamsEarthAssets::default().validate(),
+            Err(Error::InvalidParameter { name: "date", .. })
+        ));
+        assert!(matches!(
+            params().dim(-1.0).validate(),
+            Err(Error::InvalidParameter { name: "dim", .. })
+        ));
+        assert!(params().validate().is_ok());
+    }
+}
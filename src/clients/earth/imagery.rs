@@ -1,6 +1,24 @@
+use crate::{
+    error::{Error, Result},
+    prelude::*,
+    query::QueryValues,
+    transport::Response,
+};
+use chrono::NaiveDate;
 use serde::Serialize;
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize)]
+/// The first bytes of every PNG file
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Landsat 8 imagery of a location, as PNG
+pub struct EarthImagery;
+
+/// Query parameters for the Earth Imagery API
+///
+/// Built with [ParamsEarthImagery::new]; without a date the API returns the
+/// image closest to today
+#[derive(Debug, Clone, PartialEq, Serialize, QueryValues)]
 pub struct ParamsEarthImagery {
     /// Latitude
     pub lat: f64,
@@ -9,5 +27,151 @@ pub struct ParamsEarthImagery {
     /// Width and height of image in degrees
     pub dim: f64,
     /// Date of image: default to closest available to today
-    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Also calculate the percentage of the image covered by clouds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_score: Option<bool>,
+}
+
+impl Default for ParamsEarthImagery {
+    fn default() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
+
+impl ParamsEarthImagery {
+    /// Width and height the API uses when `dim` is omitted, in degrees
+    pub const DEFAULT_DIM: f64 = 0.025;
+
+    /// The latest image centred on `lat`, `lon`
+    pub fn new(lat: f64, lon: f64) -> Self {
+        Self {
+            lat,
+            lon,
+            dim: Self::DEFAULT_DIM,
+            date: None,
+            cloud_score: None,
+        }
+    }
+
+    pub fn dim(mut self, dim: f64) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    /// The image closest to `date`
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub fn cloud_score(mut self, cloud_score: bool) -> Self {
+        self.cloud_score = Some(cloud_score);
+        self
+    }
+
+    /// Reject coordinates off the globe and empty images
+    pub fn validate(&self) -> Result<()> {
+        super::validate_area(self.lat, self.lon, self.dim)
+    }
+}
+
+impl Spec for EarthImagery {
+    const BASE_URL: &'static str = "https://api.nasa.gov/planetary/earth/imagery";
+    type Params = ParamsEarthImagery;
+    type ResponseType = Vec<u8>;
+
+    fn validate(params: &ParamsEarthImagery) -> Result<()> {
+        params.validate()
+    }
+
+    /// The PNG, as sent
+    fn parse_response(res: Response) -> Result<Vec<u8>> {
+        if !res.body.starts_with(PNG_SIGNATURE) {
+            let content_type = res
+                .headers
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("no content type");
+            return Err(Error::Decode(serde::de::Error::custom(format!(
+                "expected a PNG image, got {} bytes of {}",
+                res.body.len(),
+                content_type
+            ))));
+        }
+        Ok(res.body)
+    }
+}
+
+// Implement Client Handling for EarthImagery
+impl ClientHandler<EarthImagery> for Client<EarthImagery> {}
+
+impl Client<EarthImagery> {
+    /// Fetch the image and write it to `path`, creating missing directories
+    ///
+    /// Returns the PNG bytes that were written
+    pub fn save(&self, params: &ParamsEarthImagery, path: impl AsRef<Path>) -> Result<Vec<u8>> {
+        let png = self.query(params)?;
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, &png)?;
+        Ok(png)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn params() -> ParamsEarthImagery {
+        ParamsEarthImagery::new(1.5, 100.75)
+            .date(NaiveDate::from_ymd_opt(2014, 2, 1).unwrap())
+            .dim(0.1)
+            .cloud_score(true)
+    }
+
+    #[test]
+    fn test_earth_imagery() {
+        let client = crate::transport::test_client::<EarthImagery>();
+        let png = client.query(&params()).unwrap();
+        assert!(png.starts_with(PNG_SIGNATURE));
+
+        let path = std::env::temp_dir()
+            .join(format!("nasa-rs-earth-{}", std::process::id()))
+            .join("sumatra.png");
+        assert_eq!(client.save(&params(), &path).unwrap(), png);
+        assert_eq!(std::fs::read(&path).unwrap(), png);
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn rejects_invalid_coordinates() {
+        let invalid = |params: ParamsEarthImagery, expected: &str| {
+            matches!(
+                params.validate(),
+                Err(Error::InvalidParameter { name, .. }) if name == expected
+            )
+        };
+        assert!(invalid(ParamsEarthImagery::new(90.5, 0.0), "lat"));
+        assert!(invalid(ParamsEarthImagery::new(f64::NAN, 0.0), "lat"));
+        assert!(invalid(ParamsEarthImagery::new(0.0, -180.5), "lon"));
+        assert!(invalid(ParamsEarthImagery::new(0.0, 0.0).dim(0.0), "dim"));
+        assert!(ParamsEarthImagery::new(-90.0, 180.0).validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_non_png_bodies() {
+        let client = Client::<EarthImagery>::builder()
+            .transport(|_: &crate::transport::Request| {
+                Ok(Response::json_body(
+                    r#"{"msg": "No imagery for specified date."}"#,
+                ))
+            })
+            .build()
+            .unwrap();
+        assert!(matches!(client.query(&params()), Err(Error::Decode(_))));
+    }
 }
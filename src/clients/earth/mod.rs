@@ -1 +1,26 @@
+use crate::error::{Error, Result};
+
+/// Landsat 8 scenes available for a location
+pub mod assets;
+/// Landsat 8 imagery of a location
 pub mod imagery;
+
+/// Reject coordinates off the globe and empty images
+pub(crate) fn validate_area(lat: f64, lon: f64, dim: f64) -> Result<()> {
+    let invalid = |name, reason: &str| {
+        Err(Error::InvalidParameter {
+            name,
+            reason: reason.to_owned(),
+        })
+    };
+    if !(-90.0..=90.0).contains(&lat) {
+        return invalid("lat", "must be between -90 and 90");
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        return invalid("lon", "must be between -180 and 180");
+    }
+    if !(dim.is_finite() && dim > 0.0) {
+        return invalid("dim", "must be a positive number of degrees");
+    }
+    Ok(())
+}
@@ -2,17 +2,29 @@ pub mod apod;
 pub mod donki;
 pub mod earth;
 pub mod neo;
+pub mod sbdb;
 
-pub use apod::{Apod, ApodParams};
+pub use apod::{Apod, ApodEntry, ApodParams, ApodResponse};
 pub use donki::{
-    cme::{Catalog as CmeAnalysisCatalog, CmeAnalysis, CmeAnalysisParams},
+    cme::{
+        Catalog as CmeAnalysisCatalog, Cme, CmeAnalysis, CmeAnalysisParams,
+        Keyword as CmeAnalysisKeyword,
+    },
     flr::Flr,
     gst::Gst,
     hss::Hss,
     ips::{Catalog as IpsCatalog, Ips, IpsParams, Location as IpsLocation},
     mpc::Mpc,
+    notifications::{
+        MessageType, Notification, NotificationType, Notifications, NotificationsParams,
+    },
     rbe::Rbe,
     sep::Sep,
     wsa::Wsa,
 };
-pub use neo::{Neo, NeoParams};
+pub use earth::{
+    assets::{EarthAsset, EarthAssets, ParamsEarthAssets},
+    imagery::{EarthImagery, ParamsEarthImagery},
+};
+pub use neo::{BrowsePage, NearEarthObject, Neo, NeoFeed, NeoId, NeoParams, NeoResponse};
+pub use sbdb::{Sbdb, SbdbObject, SbdbParams};
@@ -0,0 +1,236 @@
+use super::{CloseApproach, MissDistance, NearEarthObject, NeoFeed};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Joules released by one megaton of TNT
+pub const JOULES_PER_MEGATON: f64 = 4.184e15;
+/// Bulk density assumed for energy estimates (stony asteroid), kg/m³
+pub const TYPICAL_DENSITY: f64 = 2600.0;
+
+/// Units miss distances are reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceUnit {
+    /// Lunar distances (LD)
+    Lunar,
+    /// Astronomical units (AU)
+    Astronomical,
+    Kilometers,
+    Miles,
+}
+
+impl MissDistance {
+    /// The miss distance in `unit`
+    pub fn in_unit(&self, unit: DistanceUnit) -> f64 {
+        match unit {
+            DistanceUnit::Lunar => self.lunar,
+            DistanceUnit::Astronomical => self.astronomical,
+            DistanceUnit::Kilometers => self.kilometers,
+            DistanceUnit::Miles => self.miles,
+        }
+    }
+}
+
+/// One close approach of one object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Approach<'a> {
+    pub neo: &'a NearEarthObject,
+    pub approach: &'a CloseApproach,
+}
+
+impl Approach<'_> {
+    /// How close the object passed, in `unit`
+    pub fn miss_distance(&self, unit: DistanceUnit) -> f64 {
+        self.approach.miss_distance.in_unit(unit)
+    }
+
+    /// Kinetic energy at this approach's relative velocity, assuming [TYPICAL_DENSITY]
+    pub fn kinetic_energy(&self) -> EnergyRange {
+        self.kinetic_energy_with_density(TYPICAL_DENSITY)
+    }
+
+    /// Kinetic energy at this approach's relative velocity, for a bulk density in kg/m³
+    ///
+    /// The range follows the estimated diameter range; mass assumes a sphere
+    pub fn kinetic_energy_with_density(&self, density: f64) -> EnergyRange {
+        let diameter = self.neo.estimated_diameter.meters;
+        let velocity = self.approach.relative_velocity.kilometers_per_second * 1000.0;
+        let energy = |diameter: f64| {
+            let mass = density * std::f64::consts::PI / 6.0 * diameter.powi(3);
+            0.5 * mass * velocity.powi(2)
+        };
+        EnergyRange {
+            min: energy(diameter.min),
+            max: energy(diameter.max),
+        }
+    }
+}
+
+/// Kinetic energy bounds in joules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergyRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl EnergyRange {
+    /// Bounds in megatons of TNT
+    pub fn megatons(&self) -> (f64, f64) {
+        (self.min / JOULES_PER_MEGATON, self.max / JOULES_PER_MEGATON)
+    }
+}
+
+/// Every close approach listed in a feed, in date order
+pub fn approaches(feed: &NeoFeed) -> impl Iterator<Item = Approach<'_>> {
+    feed.near_earth_objects.iter().flat_map(|(date, objects)| {
+        objects.iter().flat_map(move |neo| {
+            neo.close_approach_data
+                .iter()
+                .filter(move |approach| approach.close_approach_date == *date)
+                .map(move |approach| Approach { neo, approach })
+        })
+    })
+}
+
+/// Every close approach in a feed, nearest first
+pub fn rank_by_miss_distance(feed: &NeoFeed) -> Vec<Approach<'_>> {
+    let mut ranked: Vec<_> = approaches(feed).collect();
+    sort_by_miss_distance(&mut ranked);
+    ranked
+}
+
+/// Distinct potentially hazardous objects in a feed
+pub fn hazardous(feed: &NeoFeed) -> Vec<&NearEarthObject> {
+    let mut seen = BTreeMap::new();
+    for neo in feed.near_earth_objects.values().flatten() {
+        if neo.is_potentially_hazardous_asteroid {
+            seen.entry(neo.id.as_str()).or_insert(neo);
+        }
+    }
+    seen.into_values().collect()
+}
+
+/// Each object's closest Earth approach within `dates`, nearest first
+///
+/// Works on feed results and on lookup/browse results, whose
+/// `close_approach_data` spans the object's whole known history
+pub fn closest_approaches<'a>(
+    objects: impl IntoIterator<Item = &'a NearEarthObject>,
+    dates: RangeInclusive<NaiveDate>,
+) -> Vec<Approach<'a>> {
+    let mut closest: BTreeMap<&str, Approach<'a>> = BTreeMap::new();
+    for neo in objects {
+        let Some(approach) = neo.closest_approach(dates.clone()) else {
+            continue;
+        };
+        let candidate = Approach { neo, approach };
+        closest
+            .entry(neo.id.as_str())
+            .and_modify(|best| {
+                if candidate.approach.miss_distance.kilometers
+                    < best.approach.miss_distance.kilometers
+                {
+                    *best = candidate;
+                }
+            })
+            .or_insert(candidate);
+    }
+    let mut closest: Vec<_> = closest.into_values().collect();
+    sort_by_miss_distance(&mut closest);
+    closest
+}
+
+impl NearEarthObject {
+    /// The closest approach to Earth within `dates`
+    pub fn closest_approach(&self, dates: RangeInclusive<NaiveDate>) -> Option<&CloseApproach> {
+        self.close_approach_data
+            .iter()
+            .filter(|approach| approach.orbiting_body == "Earth")
+            .filter(|approach| dates.contains(&approach.close_approach_date))
+            .min_by(|a, b| {
+                a.miss_distance
+                    .kilometers
+                    .total_cmp(&b.miss_distance.kilometers)
+            })
+    }
+}
+
+fn sort_by_miss_distance(approaches: &mut [Approach<'_>]) {
+    approaches.sort_by(|a, b| {
+        a.approach
+            .miss_distance
+            .kilometers
+            .total_cmp(&b.approach.miss_distance.kilometers)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::neo::Neo;
+    use crate::transport::ymd;
+
+    fn feed() -> NeoFeed {
+        let client = crate::transport::test_client::<Neo>();
+        client.feed(ymd(2023, 1, 1), ymd(2023, 1, 10)).unwrap()
+    }
+
+    #[test]
+    fn ranks_and_filters_feed() {
+        let feed = feed();
+        let ranked = rank_by_miss_distance(&feed);
+        assert_eq!(ranked.len(), feed.element_count as usize);
+        assert_eq!(ranked[0].neo.name, "(2023 AB1)");
+        assert!((ranked[0].miss_distance(DistanceUnit::Astronomical) - 0.0021338466).abs() < 1e-9);
+        assert!(ranked[0].miss_distance(DistanceUnit::Lunar) < 1.0);
+        assert!(ranked
+            .windows(2)
+            .all(|w| w[0].miss_distance(DistanceUnit::Kilometers)
+                <= w[1].miss_distance(DistanceUnit::Kilometers)));
+
+        let names: Vec<_> = hazardous(&feed)
+            .iter()
+            .map(|neo| neo.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["154347 (2002 XK4)", "(2010 PK9)", "(2019 AC3)"]);
+    }
+
+    #[test]
+    fn closest_approach_per_object() {
+        let feed = feed();
+        let objects = feed.near_earth_objects.values().flatten();
+        let closest = closest_approaches(objects, ymd(2023, 1, 1)..=ymd(2023, 1, 10));
+        // (2023 AB1) passes on the 2nd and 3rd, (2023 AV) on the 1st and 7th
+        assert_eq!(closest.len(), 8);
+        assert_eq!(closest[0].approach.close_approach_date, ymd(2023, 1, 2));
+        let av = closest.iter().find(|a| a.neo.name == "(2023 AV)").unwrap();
+        assert_eq!(av.approach.close_approach_date, ymd(2023, 1, 1));
+
+        let client = crate::transport::test_client::<Neo>();
+        let pk9 = client.lookup(3542519u64).unwrap();
+        let in_2023 = pk9.closest_approach(ymd(2023, 1, 1)..=ymd(2023, 12, 31));
+        assert_eq!(in_2023.unwrap().close_approach_date, ymd(2023, 1, 2));
+        let ever = pk9
+            .closest_approach(NaiveDate::MIN..=NaiveDate::MAX)
+            .unwrap();
+        assert_eq!(ever.close_approach_date, ymd(2010, 8, 4));
+    }
+
+    #[test]
+    fn estimates_kinetic_energy() {
+        let feed = feed();
+        let ranked = rank_by_miss_distance(&feed);
+        let xk4 = ranked
+            .iter()
+            .find(|a| a.neo.name == "154347 (2002 XK4)")
+            .unwrap();
+        // 1410.86 m across at 18.913 km/s: ~6.84e20 J, ~163 000 Mt
+        let energy = xk4.kinetic_energy();
+        assert!((energy.min / 6.84e20 - 1.0).abs() < 0.01);
+        let (min, max) = energy.megatons();
+        assert!((163_000.0..164_000.0).contains(&min));
+        assert!(max > min);
+        let denser = xk4.kinetic_energy_with_density(2.0 * TYPICAL_DENSITY);
+        assert!((denser.max / energy.max - 2.0).abs() < 1e-9);
+    }
+}
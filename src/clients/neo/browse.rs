@@ -0,0 +1,179 @@
+use super::{unexpected, BrowsePage, Neo, NeoParams, NeoResponse};
+use crate::{error::Result, prelude::*};
+
+/// Pages of the browse endpoint, fetched lazily
+///
+/// Requests go through [Client]'s throttle and retry policies. Iteration ends
+/// after the last page, or after the first error; [BrowsePages::next_page] is
+/// then the page that failed, to resume from with [BrowsePages::starting_at]
+pub struct BrowsePages<'a> {
+    client: &'a Client<Neo>,
+    size: Option<u32>,
+    next_page: Option<u32>,
+    failed: bool,
+}
+
+impl<'a> BrowsePages<'a> {
+    /// Continue from `page` (zero-based) instead of the first page
+    pub fn starting_at(mut self, page: u32) -> Self {
+        self.next_page = Some(page);
+        self.failed = false;
+        self
+    }
+
+    /// The page the next call to `next` fetches, or the page that failed once
+    /// iteration stopped on an error; `None` once the data-set is exhausted
+    pub fn next_page(&self) -> Option<u32> {
+        self.next_page
+    }
+}
+
+impl Iterator for BrowsePages<'_> {
+    type Item = Result<BrowsePage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let number = self.next_page?;
+        let page = match self.client.browse_page(number, self.size) {
+            Ok(page) => page,
+            Err(err) => {
+                log::warn!("stopped browsing NeoWs at page {}: {}", number, err);
+                self.failed = true;
+                return Some(Err(err));
+            }
+        };
+        let more = page.links.next.is_some() && page.page.number + 1 < page.page.total_pages;
+        self.next_page = more.then_some(page.page.number + 1);
+        Some(Ok(page))
+    }
+}
+
+impl Client<Neo> {
+    /// The first page of the overall data-set
+    pub fn browse(&self) -> Result<BrowsePage> {
+        self.browse_page(0, None)
+    }
+
+    /// One page of the overall data-set; `size` defaults to the API's 20
+    pub fn browse_page(&self, page: u32, size: Option<u32>) -> Result<BrowsePage> {
+        let params = NeoParams::Browse {
+            page: Some(page),
+            size,
+        };
+        match self.query(&params)? {
+            NeoResponse::Browse(page) => Ok(page),
+            _ => Err(unexpected("browse page")),
+        }
+    }
+
+    /// Every page of the overall data-set, starting from the first
+    ///
+    /// ```no_run
+    /// # use nasa_rs::{clients::Neo, prelude::*};
+    /// let client = Client::<Neo>::default();
+    /// let hazardous = client
+    ///     .browse_pages(Some(20))
+    ///     .starting_at(3)
+    ///     .take(2)
+    ///     .flat_map(|page| page.map(|p| p.near_earth_objects).unwrap_or_default())
+    ///     .filter(|neo| neo.is_potentially_hazardous_asteroid)
+    ///     .count();
+    /// ```
+    pub fn browse_pages(&self, size: Option<u32>) -> BrowsePages<'_> {
+        BrowsePages {
+            client: self,
+            size,
+            next_page: Some(0),
+            failed: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn follows_pages_to_the_end() {
+        let client = crate::transport::test_client::<Neo>();
+        let pages = client
+            .browse_pages(Some(2))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let numbers: Vec<_> = pages.iter().map(|page| page.page.number).collect();
+        assert_eq!(numbers, vec![0, 1, 2]);
+        let objects: usize = pages.iter().map(|page| page.near_earth_objects.len()).sum();
+        assert_eq!(objects, 5);
+    }
+
+    #[test]
+    fn stops_early_and_resumes() {
+        let client = crate::transport::test_client::<Neo>();
+        let mut pages = client.browse_pages(Some(2));
+        let first = pages.next().unwrap().unwrap();
+        assert_eq!(first.near_earth_objects.len(), 2);
+        let resume_at = pages.next_page().unwrap();
+        assert_eq!(resume_at, 1);
+
+        let rest: Vec<_> = client
+            .browse_pages(Some(2))
+            .starting_at(resume_at)
+            .map(|page| page.unwrap().page.number)
+            .collect();
+        assert_eq!(rest, vec![1, 2]);
+
+        // the first page is still what `browse` returns
+        assert_eq!(client.browse().unwrap().page.number, 0);
+    }
+
+    #[test]
+    fn stops_on_error() {
+        let client = crate::transport::test_client::<Neo>();
+        let mut pages = client.browse_pages(Some(50));
+        assert!(matches!(
+            pages.next(),
+            Some(Err(Error::InvalidParameter { name: "size", .. }))
+        ));
+        assert!(pages.next().is_none());
+        assert_eq!(pages.next_page(), Some(0));
+    }
+
+    #[test]
+    fn resumes_from_the_failed_page() {
+        use crate::transport::{Request, Response, Transport};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // page 1 is unavailable until the outage is over
+        let outage = std::sync::Arc::new(AtomicBool::new(true));
+        let down = outage.clone();
+        let cassette = crate::transport::test_cassette();
+        let client = Client::<Neo>::builder()
+            .transport(move |request: &Request| {
+                if down.load(Ordering::SeqCst) && request.url.contains("page=1&") {
+                    let mut response = Response::json_body("{}");
+                    response.status = reqwest::StatusCode::SERVICE_UNAVAILABLE;
+                    return Ok(response);
+                }
+                cassette.send(request)
+            })
+            .build()
+            .unwrap();
+
+        let mut pages = client.browse_pages(Some(2));
+        assert_eq!(pages.next().unwrap().unwrap().page.number, 0);
+        assert!(matches!(pages.next(), Some(Err(Error::Status { .. }))));
+        assert!(pages.next().is_none());
+        let resume_at = pages.next_page().unwrap();
+        assert_eq!(resume_at, 1);
+
+        outage.store(false, Ordering::SeqCst);
+        let rest: Vec<_> = pages
+            .starting_at(resume_at)
+            .map(|page| page.unwrap().page.number)
+            .collect();
+        assert_eq!(rest, vec![1, 2]);
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A near-earth object, by SPK-ID or by designation/name
+///
+/// NeoWs only looks objects up by SPK-ID; designations such as `2023 DW` are
+/// resolved through [crate::clients::Sbdb] first
+///
+/// Numbers convert the same way whether given as `u64` or text: from
+/// [NeoId::MIN_ASTEROID_SPK_ID] up they are SPK-IDs, below it asteroid numbers
+/// (`433` is Eros, SPK-ID `2000433`) and so designations. Build the variants
+/// directly to bypass the cutoff
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NeoId {
+    /// JPL SPK-ID, e.g. `3542519` or `54338723`
+    SpkId(u64),
+    /// Designation or name, e.g. `2023 DW` or `Apophis`
+    Designation(String),
+}
+
+impl NeoId {
+    /// Smallest SPK-ID assigned to an asteroid (2000000 + asteroid number);
+    /// shorter numbers are read as asteroid numbers, i.e. designations
+    pub const MIN_ASTEROID_SPK_ID: u64 = 2_000_000;
+
+    /// The SPK-ID, if no resolution is needed
+    pub fn spk_id(&self) -> Option<u64> {
+        match self {
+            Self::SpkId(id) => Some(*id),
+            Self::Designation(_) => None,
+        }
+    }
+}
+
+impl From<u64> for NeoId {
+    fn from(id: u64) -> Self {
+        if id >= Self::MIN_ASTEROID_SPK_ID {
+            Self::SpkId(id)
+        } else {
+            Self::Designation(id.to_string())
+        }
+    }
+}
+
+impl From<&str> for NeoId {
+    fn from(id: &str) -> Self {
+        let id = id.trim();
+        match id.parse::<u64>() {
+            Ok(spk_id) if spk_id >= Self::MIN_ASTEROID_SPK_ID => Self::SpkId(spk_id),
+            _ => Self::Designation(id.to_owned()),
+        }
+    }
+}
+
+impl From<String> for NeoId {
+    fn from(id: String) -> Self {
+        id.as_str().into()
+    }
+}
+
+impl std::str::FromStr for NeoId {
+    type Err = std::convert::Infallible;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Ok(id.into())
+    }
+}
+
+impl fmt::Display for NeoId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SpkId(id) => write!(f, "{id}"),
+            Self::Designation(designation) => f.write_str(designation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_either_form() {
+        assert_eq!(NeoId::from("3542519"), NeoId::SpkId(3542519));
+        assert_eq!(NeoId::from(" 54338723 "), NeoId::SpkId(54338723));
+        assert_eq!(NeoId::from("2023 DW"), NeoId::Designation("2023 DW".into()));
+        // asteroid numbers are not SPK-IDs
+        assert_eq!(NeoId::from("433"), NeoId::Designation("433".into()));
+        assert_eq!(NeoId::from(433), NeoId::from("433"));
+        assert_eq!(NeoId::from(2000433), NeoId::from("2000433"));
+        assert_eq!(NeoId::from(2000433).to_string(), "2000433");
+        assert_eq!("Apophis".parse::<NeoId>().unwrap().spk_id(), None);
+    }
+}
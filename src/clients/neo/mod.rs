@@ -0,0 +1,419 @@
+use crate::{clients::Sbdb, error::Error, prelude::*, query::QueryValues};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// Ranking, filtering and energy estimates over close approaches
+pub mod analytics;
+/// Paging through the overall data-set
+pub mod browse;
+mod id;
+mod models;
+/// Two-body propagation of `orbital_data`
+pub mod orbit;
+pub use browse::BrowsePages;
+pub use id::NeoId;
+pub use models::{
+    BrowsePage, CloseApproach, DiameterRange, EstimatedDiameter, Links, MissDistance,
+    NearEarthObject, NeoFeed, NeoResponse, OrbitClass, OrbitalData, PageInfo, RelativeVelocity,
+};
+
+#[derive(Debug, Clone, Serialize, QueryValues)]
+pub enum NeoParams {
+    /// Retrieve a list of Asteroids based on their closest approach date to Earth.
+    Feed {
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    },
+    /// Lookup a specific Asteroid based on its [NASA JPL small body (SPK-ID) ID](http://ssd.jpl.nasa.gov/sbdb_query.cgi)
+    ///
+    /// Designations must be resolved first; [Client::lookup] does so
+    Lookup { asteroid_id: NeoId },
+    /// Browse the overall Asteroid data-set, one page at a time
+    Browse {
+        /// Zero-based page number; `page=0` is sent for `None`, so the values
+        /// alone always identify a browse request
+        #[query(default = "0")]
+        page: Option<u32>,
+        /// Objects per page, at most [NeoParams::MAX_BROWSE_SIZE]
+        size: Option<u32>,
+    },
+}
+
+impl Default for NeoParams {
+    fn default() -> Self {
+        Self::Feed {
+            start_date: Some(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()),
+            end_date: Some(NaiveDate::from_ymd_opt(2023, 1, 3).unwrap()),
+        }
+    }
+}
+
+/// Asteroids Near-Earth Objects
+pub struct Neo;
+
+impl NeoParams {
+    /// Longest span the feed endpoint accepts, in days between `start_date` and `end_date`
+    pub const MAX_FEED_DAYS: i64 = 7;
+    /// Largest page the browse endpoint returns
+    pub const MAX_BROWSE_SIZE: u32 = 20;
+
+    /// Split `start..=end` into consecutive feed windows the API accepts
+    pub fn feed_windows(start: NaiveDate, end: NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+        let span = chrono::Duration::days(Self::MAX_FEED_DAYS);
+        let mut windows = Vec::new();
+        let mut from = start;
+        while from <= end {
+            let to = std::cmp::min(from + span, end);
+            windows.push((from, to));
+            from = to + chrono::Duration::days(1);
+        }
+        windows
+    }
+}
+
+impl Spec for Neo {
+    const BASE_URL: &'static str = "https://api.nasa.gov/neo/rest/v1/";
+    type Params = NeoParams;
+    type ResponseType = NeoResponse;
+
+    /// Decode into the model for the endpoint `url` points at
+    fn parse_response_for(
+        url: &str,
+        res: crate::transport::Response,
+    ) -> crate::error::Result<NeoResponse> {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        Ok(if path.ends_with("/feed") {
+            NeoResponse::Feed(res.json()?)
+        } else if path.ends_with("/neo/browse") {
+            NeoResponse::Browse(res.json()?)
+        } else {
+            NeoResponse::Lookup(Box::new(res.json()?))
+        })
+    }
+
+    fn validate(params: &NeoParams) -> crate::error::Result<()> {
+        match params {
+            NeoParams::Feed {
+                start_date: Some(start),
+                end_date: Some(end),
+            } => {
+                if start > end {
+                    return Err(Error::InvalidParameter {
+                        name: "start_date",
+                        reason: "must not be after end_date".to_owned(),
+                    });
+                }
+                if (*end - *start).num_days() > NeoParams::MAX_FEED_DAYS {
+                    return Err(Error::InvalidParameter {
+                        name: "end_date",
+                        reason: "must be at most 7 days after start_date; use Client::feed"
+                            .to_owned(),
+                    });
+                }
+            }
+            NeoParams::Browse {
+                size: Some(size), ..
+            } if *size == 0 || *size > NeoParams::MAX_BROWSE_SIZE => {
+                return Err(Error::InvalidParameter {
+                    name: "size",
+                    reason: "must be between 1 and 20".to_owned(),
+                });
+            }
+            NeoParams::Lookup {
+                asteroid_id: NeoId::Designation(designation),
+            } => {
+                return Err(Error::InvalidParameter {
+                    name: "asteroid_id",
+                    reason: format!("{designation:?} is not an SPK-ID; use Client::lookup"),
+                });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Implement the client handler for Neo
+///
+/// Each request kind has its own path under [Neo::BASE_URL](Spec::BASE_URL)
+impl ClientHandler<Neo> for Client<Neo> {
+    fn build_query(
+        config: &ClientConfig,
+        params: <Neo as Spec>::Params,
+    ) -> crate::error::Result<String> {
+        let path = match &params {
+            NeoParams::Feed { .. } => "feed",
+            NeoParams::Lookup { .. } => "neo",
+            NeoParams::Browse { .. } => "neo/browse",
+        };
+        neo_url(config, path, params.values()?)
+    }
+
+    /// `asteroid_id` looks an object up, `page` browses, anything else queries
+    /// the feed; [NeoParams] values always carry the key for their route
+    fn build_query_with(
+        config: &ClientConfig,
+        values: std::collections::HashMap<String, String>,
+    ) -> crate::error::Result<String> {
+        let path = if values.contains_key("asteroid_id") {
+            "neo"
+        } else if values.contains_key("page") {
+            "neo/browse"
+        } else {
+            "feed"
+        };
+        neo_url(config, path, values)
+    }
+}
+
+/// The URL for `path`, with `asteroid_id` moved from the query into the path
+fn neo_url(
+    config: &ClientConfig,
+    path: &str,
+    mut values: std::collections::HashMap<String, String>,
+) -> crate::error::Result<String> {
+    let mut endpoint = config.endpoint(<Neo as Spec>::BASE_URL) + path;
+    if let Some(asteroid_id) = values.remove("asteroid_id") {
+        endpoint.push('/');
+        endpoint.push_str(&crate::query::encode(&asteroid_id));
+    }
+    let url = crate::query::build_url(&endpoint, values, Some(config.api_key()))?;
+
+    log::debug!("Built query bound for: {}", url);
+
+    Ok(url)
+}
+
+impl Client<Neo> {
+    /// Asteroids by closest approach date, for `start..=end`
+    ///
+    /// Ranges longer than the API allows are fetched one window at a time
+    /// (see [NeoParams::feed_windows]) and merged into a single feed
+    pub fn feed(&self, start: NaiveDate, end: NaiveDate) -> crate::error::Result<NeoFeed> {
+        if start > end {
+            return Err(Error::InvalidParameter {
+                name: "start_date",
+                reason: "must not be after end_date".to_owned(),
+            });
+        }
+        let mut merged: Option<NeoFeed> = None;
+        for (start, end) in NeoParams::feed_windows(start, end) {
+            let params = NeoParams::Feed {
+                start_date: Some(start),
+                end_date: Some(end),
+            };
+            let feed = match self.query(&params)? {
+                NeoResponse::Feed(feed) => feed,
+                _ => return Err(unexpected("feed")),
+            };
+            match merged.as_mut() {
+                Some(merged) => merged.merge(feed),
+                None => merged = Some(feed),
+            }
+        }
+        Ok(merged.expect("a non-empty range has at least one window"))
+    }
+
+    /// A single asteroid by SPK-ID or designation
+    pub fn lookup(&self, id: impl Into<NeoId>) -> crate::error::Result<NearEarthObject> {
+        let asteroid_id = NeoId::SpkId(self.resolve(&id.into())?);
+        match self.query(&NeoParams::Lookup { asteroid_id })? {
+            NeoResponse::Lookup(neo) => Ok(*neo),
+            _ => Err(unexpected("near-earth object")),
+        }
+    }
+
+    /// The SPK-ID for `id`, asking the Small-Body Database for designations
+    pub fn resolve(&self, id: &NeoId) -> crate::error::Result<u64> {
+        let designation = match id {
+            NeoId::SpkId(id) => return Ok(*id),
+            NeoId::Designation(designation) => designation,
+        };
+        let object = self.with_spec::<Sbdb>().object(designation)?;
+        object.spkid.parse().map_err(|_| Error::InvalidParameter {
+            name: "asteroid_id",
+            reason: format!("{designation:?} resolved to a non-numeric SPK-ID"),
+        })
+    }
+}
+
+pub(super) fn unexpected(expected: &str) -> Error {
+    Error::Decode(serde::de::Error::custom(format!(
+        "expected a NeoWs {expected}"
+    )))
+}
+
+#[cfg(test)]
+mod test_neo {
+    use super::*;
+    use crate::transport::ymd;
+
+    #[test]
+    fn test_neo() {
+        pretty_env_logger::try_init().ok();
+
+        let client = crate::transport::test_client::<Neo>();
+        let params = NeoParams::default();
+        let response = client.query(&params).unwrap();
+        let prtty = serde_json::to_string_pretty(&response).unwrap();
+        println!("{}", prtty);
+        let NeoResponse::Feed(feed) = response else {
+            panic!("expected a feed, got {response:?}");
+        };
+        assert_eq!(feed.element_count, 8);
+        assert_eq!(feed.near_earth_objects.len(), 3);
+        let total: usize = feed.near_earth_objects.values().map(Vec::len).sum();
+        assert_eq!(total, 8);
+        assert!(feed.links.prev.is_some() && feed.links.next.is_some());
+
+        let neo = &feed.near_earth_objects[&ymd(2023, 1, 1)][0];
+        let approach = &neo.close_approach_data[0];
+        assert_eq!(approach.close_approach_date, ymd(2023, 1, 1));
+        assert_eq!(approach.orbiting_body, "Earth");
+        assert!(approach.miss_distance.lunar > 0.0);
+        assert!(approach.relative_velocity.kilometers_per_second > 0.0);
+        let diameter = neo.estimated_diameter.kilometers;
+        assert!(diameter.min < diameter.max);
+        assert!(neo.orbital_data.is_none());
+    }
+
+    #[test]
+    fn test_neo_lookup() {
+        let client = crate::transport::test_client::<Neo>();
+        let neo = client.lookup(3542519u64).unwrap();
+        assert_eq!(neo.id, "3542519");
+        assert!(neo.designation.is_some());
+        assert!(neo.close_approach_data.len() > 1);
+        let orbit = neo.orbital_data.unwrap();
+        assert!(orbit.eccentricity > 0.0 && orbit.eccentricity < 1.0);
+        assert!(!orbit.orbit_class.orbit_class_type.is_empty());
+        assert!(client.feed(ymd(2023, 1, 1), ymd(2023, 1, 3)).is_ok());
+    }
+
+    #[test]
+    fn test_neo_lookup_designation() {
+        let client = crate::transport::test_client::<Neo>();
+        let neo = client.lookup("2023 DW").unwrap();
+        assert_eq!(neo.id, "54338723");
+        assert_eq!(neo.designation.as_deref(), Some("2023 DW"));
+        assert_eq!(client.lookup("54338723").unwrap(), neo);
+        assert!(client.lookup("2099 ZZ999").is_err());
+
+        let params = NeoParams::Lookup {
+            asteroid_id: "2023 DW".into(),
+        };
+        assert!(matches!(
+            client.query(&params),
+            Err(Error::InvalidParameter {
+                name: "asteroid_id",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_neo_browse() {
+        let client = crate::transport::test_client::<Neo>();
+        let page = client.browse().unwrap();
+        assert_eq!(page.page.number, 0);
+        assert_eq!(page.page.size, 20);
+        assert!(page.page.total_pages > 1);
+        assert!(page.links.next.is_some());
+        assert!(page
+            .near_earth_objects
+            .iter()
+            .all(|neo| neo.orbital_data.is_some()));
+    }
+
+    #[test]
+    fn decodes_by_endpoint() {
+        let client = Client::<Neo>::builder()
+            .transport(|_: &crate::transport::Request| {
+                Ok(crate::transport::Response::json_body(
+                    r#"{"links": {}, "element_count": "many", "near_earth_objects": {}}"#,
+                ))
+            })
+            .build()
+            .unwrap();
+        let Err(Error::Decode(err)) = client.query(&NeoParams::default()) else {
+            panic!("expected a decode error");
+        };
+        // the feed model's own error, not "did not match any variant"
+        assert!(err.to_string().contains("expected u32"), "{err}");
+        assert!(matches!(client.lookup(3542519u64), Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn builds_paths_for_both_query_paths() {
+        let (client, urls) = crate::transport::recording_client::<Neo>();
+        let lookup = NeoParams::Lookup {
+            asteroid_id: NeoId::SpkId(3542519),
+        };
+        let browse = NeoParams::Browse {
+            page: Some(1),
+            size: Some(2),
+        };
+        let first_page = NeoParams::Browse {
+            page: None,
+            size: None,
+        };
+        for params in [NeoParams::default(), lookup, browse, first_page] {
+            client.query(&params).ok();
+            client.query_with(params.values().unwrap()).ok();
+        }
+        let urls = urls.lock().unwrap();
+        let base = "https://api.nasa.gov/neo/rest/v1";
+        assert_eq!(
+            urls[0],
+            format!("{base}/feed?end_date=2023-01-03&start_date=2023-01-01&api_key=test-key")
+        );
+        assert_eq!(urls[2], format!("{base}/neo/3542519?api_key=test-key"));
+        assert_eq!(
+            urls[4],
+            format!("{base}/neo/browse?page=1&size=2&api_key=test-key")
+        );
+        assert_eq!(
+            urls[6],
+            format!("{base}/neo/browse?page=0&api_key=test-key")
+        );
+        for pair in urls.chunks(2) {
+            assert_eq!(pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_neo_feed_chunks() {
+        let windows = NeoParams::feed_windows(ymd(2023, 1, 1), ymd(2023, 1, 10));
+        assert_eq!(
+            windows,
+            vec![
+                (ymd(2023, 1, 1), ymd(2023, 1, 8)),
+                (ymd(2023, 1, 9), ymd(2023, 1, 10))
+            ]
+        );
+        assert_eq!(
+            NeoParams::feed_windows(ymd(2023, 1, 1), ymd(2023, 1, 1)),
+            vec![(ymd(2023, 1, 1), ymd(2023, 1, 1))]
+        );
+
+        let client = crate::transport::test_client::<Neo>();
+        let feed = client.feed(ymd(2023, 1, 1), ymd(2023, 1, 10)).unwrap();
+        assert_eq!(feed.near_earth_objects.len(), 10);
+        assert_eq!(feed.near_earth_objects[&ymd(2023, 1, 9)].len(), 1);
+        assert_eq!(feed.element_count, 13);
+        assert!(feed.links.next.unwrap().contains("start_date=2023-01-11"));
+
+        let params = NeoParams::Feed {
+            start_date: Some(ymd(2023, 1, 1)),
+            end_date: Some(ymd(2023, 1, 10)),
+        };
+        assert!(matches!(
+            client.query(&params),
+            Err(Error::InvalidParameter {
+                name: "end_date",
+                ..
+            })
+        ));
+        assert!(client.feed(ymd(2023, 1, 2), ymd(2023, 1, 1)).is_err());
+    }
+}
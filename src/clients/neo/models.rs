@@ -0,0 +1,252 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+/// Pagination links; object records only carry `self`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Links {
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub self_: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(default, alias = "previous", skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+}
+
+/// Response of the feed endpoint: objects grouped by close-approach date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NeoFeed {
+    pub links: Links,
+    pub element_count: u32,
+    pub near_earth_objects: BTreeMap<NaiveDate, Vec<NearEarthObject>>,
+}
+
+impl NeoFeed {
+    /// Fold a later window into this feed
+    ///
+    /// Objects already listed under the same date are skipped, and
+    /// `element_count` is recounted; the links span both windows
+    pub fn merge(&mut self, other: NeoFeed) {
+        for (date, objects) in other.near_earth_objects {
+            let listed = self.near_earth_objects.entry(date).or_default();
+            for neo in objects {
+                if !listed.iter().any(|seen| seen.id == neo.id) {
+                    listed.push(neo);
+                }
+            }
+        }
+        self.element_count = self
+            .near_earth_objects
+            .values()
+            .map(Vec::len)
+            .sum::<usize>() as u32;
+        self.links.self_ = None;
+        self.links.next = other.links.next;
+    }
+}
+
+/// Page metadata of the browse endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageInfo {
+    pub size: u32,
+    pub total_elements: u64,
+    pub total_pages: u32,
+    /// Zero-based page number
+    pub number: u32,
+}
+
+/// Response of the browse endpoint: one page of the whole catalog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowsePage {
+    pub links: Links,
+    pub page: PageInfo,
+    pub near_earth_objects: Vec<NearEarthObject>,
+}
+
+/// A near-earth object, as returned by all three endpoints
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NearEarthObject {
+    #[serde(default)]
+    pub links: Links,
+    /// SPK-ID
+    pub id: String,
+    pub neo_reference_id: String,
+    pub name: String,
+    /// Only present in lookup and browse results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub designation: Option<String>,
+    pub nasa_jpl_url: String,
+    pub absolute_magnitude_h: f64,
+    pub estimated_diameter: EstimatedDiameter,
+    pub is_potentially_hazardous_asteroid: bool,
+    pub close_approach_data: Vec<CloseApproach>,
+    /// Only present in lookup and browse results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orbital_data: Option<OrbitalData>,
+    pub is_sentry_object: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentry_data: Option<String>,
+}
+
+/// Estimated diameter range in every unit the API reports
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EstimatedDiameter {
+    pub kilometers: DiameterRange,
+    pub meters: DiameterRange,
+    pub miles: DiameterRange,
+    pub feet: DiameterRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DiameterRange {
+    #[serde(rename = "estimated_diameter_min")]
+    pub min: f64,
+    #[serde(rename = "estimated_diameter_max")]
+    pub max: f64,
+}
+
+/// One close approach to a body (usually Earth)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CloseApproach {
+    pub close_approach_date: NaiveDate,
+    /// e.g. `2023-Jan-01 08:53`; missing for some historic approaches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_approach_date_full: Option<String>,
+    /// Milliseconds since the unix epoch
+    pub epoch_date_close_approach: i64,
+    pub relative_velocity: RelativeVelocity,
+    pub miss_distance: MissDistance,
+    pub orbiting_body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RelativeVelocity {
+    #[serde(deserialize_with = "number")]
+    pub kilometers_per_second: f64,
+    #[serde(deserialize_with = "number")]
+    pub kilometers_per_hour: f64,
+    #[serde(deserialize_with = "number")]
+    pub miles_per_hour: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MissDistance {
+    #[serde(deserialize_with = "number")]
+    pub astronomical: f64,
+    #[serde(deserialize_with = "number")]
+    pub lunar: f64,
+    #[serde(deserialize_with = "number")]
+    pub kilometers: f64,
+    #[serde(deserialize_with = "number")]
+    pub miles: f64,
+}
+
+/// Orbit solution from JPL's Small-Body Database; angles in degrees,
+/// distances in AU, epochs as Julian dates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrbitalData {
+    pub orbit_id: String,
+    pub orbit_determination_date: String,
+    pub first_observation_date: NaiveDate,
+    pub last_observation_date: NaiveDate,
+    pub data_arc_in_days: Option<u32>,
+    pub observations_used: u32,
+    pub orbit_uncertainty: String,
+    #[serde(deserialize_with = "number")]
+    pub minimum_orbit_intersection: f64,
+    #[serde(deserialize_with = "number")]
+    pub jupiter_tisserand_invariant: f64,
+    #[serde(deserialize_with = "number")]
+    pub epoch_osculation: f64,
+    #[serde(deserialize_with = "number")]
+    pub eccentricity: f64,
+    #[serde(deserialize_with = "number")]
+    pub semi_major_axis: f64,
+    #[serde(deserialize_with = "number")]
+    pub inclination: f64,
+    #[serde(deserialize_with = "number")]
+    pub ascending_node_longitude: f64,
+    /// Days
+    #[serde(deserialize_with = "number")]
+    pub orbital_period: f64,
+    #[serde(deserialize_with = "number")]
+    pub perihelion_distance: f64,
+    #[serde(deserialize_with = "number")]
+    pub perihelion_argument: f64,
+    #[serde(deserialize_with = "number")]
+    pub aphelion_distance: f64,
+    #[serde(deserialize_with = "number")]
+    pub perihelion_time: f64,
+    #[serde(deserialize_with = "number")]
+    pub mean_anomaly: f64,
+    /// Degrees per day
+    #[serde(deserialize_with = "number")]
+    pub mean_motion: f64,
+    pub equinox: String,
+    pub orbit_class: OrbitClass,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrbitClass {
+    /// e.g. `APO`, `AMO`, `ATE`
+    pub orbit_class_type: String,
+    pub orbit_class_description: String,
+    pub orbit_class_range: String,
+}
+
+/// Whichever shape the request asked for
+///
+/// Responses are decoded by endpoint (see [Neo](super::Neo)), so a malformed body
+/// reports the field that failed; the untagged form only serves (de)serializing
+/// stored responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NeoResponse {
+    Feed(NeoFeed),
+    Browse(BrowsePage),
+    Lookup(Box<NearEarthObject>),
+}
+
+/// NeoWs sends most measurements as strings (`"18.9130297472"`)
+fn number<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Number {
+        Float(f64),
+        String(String),
+    }
+    match Number::deserialize(deserializer)? {
+        Number::Float(value) => Ok(value),
+        Number::String(value) => value.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clients::neo::Neo;
+    use crate::transport::ymd;
+
+    #[test]
+    fn merge_skips_objects_listed_twice() {
+        let client = crate::transport::test_client::<Neo>();
+        let mut week = client.feed(ymd(2023, 1, 1), ymd(2023, 1, 8)).unwrap();
+        let mut later = client.feed(ymd(2023, 1, 9), ymd(2023, 1, 10)).unwrap();
+        assert_eq!((week.element_count, later.element_count), (11, 2));
+
+        // windows that overlap on the 8th list its object twice
+        let overlap = ymd(2023, 1, 8);
+        later
+            .near_earth_objects
+            .insert(overlap, week.near_earth_objects[&overlap].clone());
+        let next = later.links.next.clone();
+        week.merge(later);
+        assert_eq!(week.near_earth_objects[&overlap].len(), 1);
+        assert_eq!(week.near_earth_objects.len(), 10);
+        assert_eq!(week.element_count, 13);
+        assert!(week.links.self_.is_none());
+        assert_eq!(week.links.next, next);
+    }
+}
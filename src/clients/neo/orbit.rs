@@ -0,0 +1,328 @@
+use super::OrbitalData;
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+
+/// Gaussian gravitational constant, √(GM☉) in AU^1.5/day
+pub const GAUSSIAN_GRAVITATIONAL_CONSTANT: f64 = 0.01720209895;
+/// GM of the Sun in AU³/day²
+pub const GM_SUN: f64 = GAUSSIAN_GRAVITATIONAL_CONSTANT * GAUSSIAN_GRAVITATIONAL_CONSTANT;
+/// Julian date of J2000.0
+pub const J2000: f64 = 2451545.0;
+
+/// Julian date of a UTC instant
+///
+/// UTC is used in place of TDB; the ~70 s difference is far below the
+/// accuracy of two-body propagation
+pub fn julian_date(time: DateTime<Utc>) -> f64 {
+    time.timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5
+}
+
+/// Osculating heliocentric elements, referred to the J2000 ecliptic
+///
+/// Angles are in degrees and distances in AU. `semi_major_axis` is negative
+/// for hyperbolic orbits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Julian date the elements are valid for
+    pub epoch: f64,
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub ascending_node_longitude: f64,
+    pub perihelion_argument: f64,
+    /// Mean anomaly at `epoch`
+    pub mean_anomaly: f64,
+}
+
+/// Position (AU) and velocity (AU/day), heliocentric ecliptic J2000
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateVector {
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+impl StateVector {
+    /// Distance from the Sun in AU
+    pub fn distance(&self) -> f64 {
+        norm(self.position)
+    }
+
+    /// Speed in AU/day
+    pub fn speed(&self) -> f64 {
+        norm(self.velocity)
+    }
+
+    /// Distance to another body's position in AU
+    pub fn distance_to(&self, other: &StateVector) -> f64 {
+        norm(sub(self.position, other.position))
+    }
+}
+
+impl OrbitalElements {
+    /// Largest distance from 1 at which an eccentricity is treated as parabolic
+    /// and rejected; neither solver converges reliably that close
+    pub const PARABOLIC_TOLERANCE: f64 = 1e-8;
+
+    /// Earth-Moon barycenter's mean elements at `jd`
+    ///
+    /// From JPL's low-precision approximate elements (1800-2050), good to
+    /// roughly 1e-4 AU
+    pub fn earth(jd: f64) -> Self {
+        let t = (jd - J2000) / 36525.0;
+        let inclination = -0.00001531 - 0.01294668 * t;
+        let mean_longitude = 100.46457166 + 35999.37244981 * t;
+        let perihelion_longitude = 102.93768193 + 0.32327364 * t;
+        let ascending_node_longitude = 0.0;
+        Self {
+            epoch: jd,
+            semi_major_axis: 1.00000261 + 0.00000562 * t,
+            eccentricity: 0.01671123 - 0.00004392 * t,
+            inclination,
+            ascending_node_longitude,
+            perihelion_argument: perihelion_longitude - ascending_node_longitude,
+            mean_anomaly: mean_longitude - perihelion_longitude,
+        }
+    }
+
+    /// Perihelion distance in AU
+    pub fn perihelion_distance(&self) -> f64 {
+        self.semi_major_axis * (1.0 - self.eccentricity)
+    }
+
+    /// Mean motion in radians per day
+    pub fn mean_motion(&self) -> f64 {
+        GAUSSIAN_GRAVITATIONAL_CONSTANT / self.semi_major_axis.abs().powf(1.5)
+    }
+
+    /// Orbital period in days; `None` for hyperbolic orbits
+    pub fn period(&self) -> Option<f64> {
+        (self.eccentricity < 1.0).then(|| std::f64::consts::TAU / self.mean_motion())
+    }
+
+    /// Two-body state at Julian date `jd`
+    pub fn state_at(&self, jd: f64) -> StateVector {
+        let e = self.eccentricity;
+        let a = self.semi_major_axis;
+        let n = self.mean_motion();
+        let mean_anomaly = self.mean_anomaly.to_radians() + n * (jd - self.epoch);
+
+        // position and velocity in the orbital plane, x towards perihelion
+        let (x, y, vx, vy) = if e < 1.0 {
+            let m = mean_anomaly.rem_euclid(std::f64::consts::TAU);
+            let big_e = solve_elliptic(m, e);
+            let (sin, cos) = big_e.sin_cos();
+            let b = a * (1.0 - e * e).sqrt();
+            let rate = n / (1.0 - e * cos);
+            (a * (cos - e), b * sin, -a * sin * rate, b * cos * rate)
+        } else {
+            let h = solve_hyperbolic(mean_anomaly, e);
+            let (sinh, cosh) = (h.sinh(), h.cosh());
+            let a = a.abs();
+            let b = a * (e * e - 1.0).sqrt();
+            let rate = n / (e * cosh - 1.0);
+            (a * (e - cosh), b * sinh, -a * sinh * rate, b * cosh * rate)
+        };
+
+        let rotate = perifocal_to_ecliptic(
+            self.ascending_node_longitude.to_radians(),
+            self.inclination.to_radians(),
+            self.perihelion_argument.to_radians(),
+        );
+        StateVector {
+            position: rotate(x, y),
+            velocity: rotate(vx, vy),
+        }
+    }
+
+    /// Approximate distance from Earth in AU at Julian date `jd`
+    pub fn earth_distance(&self, jd: f64) -> f64 {
+        self.state_at(jd).distance_to(&Self::earth(jd).state_at(jd))
+    }
+
+    /// `steps + 1` evenly spaced states from `start` to `end` (Julian dates), for plotting
+    pub fn trajectory(&self, start: f64, end: f64, steps: usize) -> Vec<(f64, StateVector)> {
+        let steps = steps.max(1);
+        (0..=steps)
+            .map(|i| {
+                let jd = start + (end - start) * i as f64 / steps as f64;
+                (jd, self.state_at(jd))
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&OrbitalData> for OrbitalElements {
+    type Error = Error;
+
+    fn try_from(data: &OrbitalData) -> Result<Self, Self::Error> {
+        let e = data.eccentricity;
+        if e.is_nan() || e < 0.0 || (e - 1.0).abs() < Self::PARABOLIC_TOLERANCE {
+            return Err(Error::InvalidParameter {
+                name: "eccentricity",
+                reason: format!("{e} is not an elliptic or hyperbolic orbit"),
+            });
+        }
+        if data.perihelion_distance.is_nan() || data.perihelion_distance <= 0.0 {
+            return Err(Error::InvalidParameter {
+                name: "perihelion_distance",
+                reason: "must be positive".to_owned(),
+            });
+        }
+        Ok(Self {
+            epoch: data.epoch_osculation,
+            // q is reported for every orbit; `a` is meaningless near e = 1
+            semi_major_axis: data.perihelion_distance / (1.0 - e),
+            eccentricity: e,
+            inclination: data.inclination,
+            ascending_node_longitude: data.ascending_node_longitude,
+            perihelion_argument: data.perihelion_argument,
+            mean_anomaly: data.mean_anomaly,
+        })
+    }
+}
+
+/// E - e·sin(E) = M, by Newton's method
+fn solve_elliptic(m: f64, e: f64) -> f64 {
+    let mut big_e = if e < 0.8 { m } else { std::f64::consts::PI };
+    for _ in 0..50 {
+        let step = (big_e - e * big_e.sin() - m) / (1.0 - e * big_e.cos());
+        big_e -= step;
+        if step.abs() < 1e-14 {
+            break;
+        }
+    }
+    big_e
+}
+
+/// e·sinh(H) - H = M, by Newton's method
+fn solve_hyperbolic(m: f64, e: f64) -> f64 {
+    let mut h = (2.0 * m.abs() / e + 1.8).ln().copysign(m);
+    for _ in 0..100 {
+        let step = (e * h.sinh() - h - m) / (e * h.cosh() - 1.0);
+        h -= step;
+        if step.abs() < 1e-14 {
+            break;
+        }
+    }
+    h
+}
+
+fn perifocal_to_ecliptic(
+    node: f64,
+    inclination: f64,
+    argument: f64,
+) -> impl Fn(f64, f64) -> [f64; 3] {
+    let (sin_o, cos_o) = node.sin_cos();
+    let (sin_i, cos_i) = inclination.sin_cos();
+    let (sin_w, cos_w) = argument.sin_cos();
+    let p = [
+        cos_o * cos_w - sin_o * sin_w * cos_i,
+        sin_o * cos_w + cos_o * sin_w * cos_i,
+        sin_w * sin_i,
+    ];
+    let q = [
+        -cos_o * sin_w - sin_o * cos_w * cos_i,
+        -sin_o * sin_w + cos_o * cos_w * cos_i,
+        cos_w * sin_i,
+    ];
+    move |x, y| {
+        [
+            p[0] * x + q[0] * y,
+            p[1] * x + q[1] * y,
+            p[2] * x + q[2] * y,
+        ]
+    }
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::neo::Neo;
+
+    /// Specific orbital energy, which two-body motion conserves
+    fn energy(state: &StateVector) -> f64 {
+        state.speed().powi(2) / 2.0 - GM_SUN / state.distance()
+    }
+
+    #[test]
+    fn propagates_elliptic_orbits() {
+        let client = crate::transport::test_client::<Neo>();
+        let neo = client.lookup(3542519u64).unwrap();
+        let data = neo.orbital_data.as_ref().unwrap();
+        let elements = OrbitalElements::try_from(data).unwrap();
+        assert!((elements.semi_major_axis - data.semi_major_axis).abs() < 1e-9);
+        let period = elements.period().unwrap();
+        assert!((period - data.orbital_period).abs() < 0.01);
+
+        let expected = -GM_SUN / (2.0 * elements.semi_major_axis);
+        let path = elements.trajectory(elements.epoch, elements.epoch + period, 64);
+        assert_eq!(path.len(), 65);
+        for (_, state) in &path {
+            assert!((energy(state) / expected - 1.0).abs() < 1e-9);
+            let r = state.distance();
+            assert!(r >= data.perihelion_distance - 1e-9 && r <= data.aphelion_distance + 1e-9);
+        }
+        // back where it started after one period
+        let (start, end) = (path[0].1, path[64].1);
+        assert!(start.distance_to(&end) < 1e-9);
+
+        // at perihelion time the object sits at perihelion
+        let perihelion = elements.state_at(data.perihelion_time);
+        assert!((perihelion.distance() - data.perihelion_distance).abs() < 1e-6);
+    }
+
+    #[test]
+    fn propagates_hyperbolic_orbits() {
+        // roughly 1I/ʻOumuamua
+        let elements = OrbitalElements {
+            epoch: 2458080.5,
+            semi_major_axis: -1.272,
+            eccentricity: 1.2011,
+            inclination: 122.74,
+            ascending_node_longitude: 24.60,
+            perihelion_argument: 241.81,
+            mean_anomaly: 36.43,
+        };
+        assert!(elements.period().is_none());
+        let q = elements.perihelion_distance();
+        assert!((q - 0.2558).abs() < 1e-3);
+
+        let expected = GM_SUN / (2.0 * elements.semi_major_axis.abs());
+        let perihelion_time =
+            elements.epoch - elements.mean_anomaly.to_radians() / elements.mean_motion();
+        for days in [-400.0, -30.0, 0.0, 30.0, 400.0] {
+            let state = elements.state_at(perihelion_time + days);
+            assert!((energy(&state) / expected - 1.0).abs() < 1e-9);
+            assert!(state.distance() >= q - 1e-9);
+        }
+        assert!((elements.state_at(perihelion_time).distance() - q).abs() < 1e-9);
+    }
+
+    #[test]
+    fn approximates_earth() {
+        // perihelion in early January, aphelion in early July
+        let january = julian_date("2024-01-03T00:00:00Z".parse().unwrap());
+        let july = julian_date("2024-07-05T00:00:00Z".parse().unwrap());
+        let earth = |jd| OrbitalElements::earth(jd).state_at(jd).distance();
+        assert!((earth(january) - 0.98333).abs() < 2e-4);
+        assert!((earth(july) - 1.01673).abs() < 2e-4);
+        assert!(OrbitalElements::earth(january).earth_distance(january) < 1e-12);
+        assert_eq!(julian_date("2000-01-01T12:00:00Z".parse().unwrap()), J2000);
+    }
+
+    #[test]
+    fn rejects_parabolic_orbits() {
+        let client = crate::transport::test_client::<Neo>();
+        let mut data = client.lookup(3542519u64).unwrap().orbital_data.unwrap();
+        data.eccentricity = 1.0;
+        assert!(OrbitalElements::try_from(&data).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{error::Error, prelude::*, query::QueryValues};
+use serde::{Deserialize, Serialize};
+
+/// JPL Small-Body Database (SBDB) object lookup
+///
+/// Hosted by JPL rather than api.nasa.gov, so no API key is sent
+pub struct Sbdb;
+
+/// Query parameters for the SBDB API
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, QueryValues)]
+pub struct SbdbParams {
+    /// Search string: SPK-ID, designation or name (e.g. `2023 DW`, `Apophis`)
+    pub sstr: String,
+}
+
+impl SbdbParams {
+    pub fn search(sstr: impl Into<String>) -> Self {
+        Self { sstr: sstr.into() }
+    }
+}
+
+/// The object a search resolved to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SbdbObject {
+    /// SPK-ID, as used by NeoWs
+    pub spkid: String,
+    /// e.g. `99942 Apophis (2004 MN4)`
+    pub fullname: String,
+    /// Primary designation
+    pub des: String,
+    /// `an` numbered asteroid, `au` unnumbered asteroid, `cn`/`cu` comets
+    #[serde(default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub orbit_class: Option<SbdbOrbitClass>,
+    #[serde(default)]
+    pub neo: bool,
+    #[serde(default)]
+    pub pha: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SbdbOrbitClass {
+    pub code: String,
+    pub name: String,
+}
+
+/// One candidate when a search matches several objects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SbdbMatch {
+    pub pdes: String,
+    pub name: String,
+}
+
+/// A lookup result: `object` when found, otherwise `message` (and `list`
+/// when the search was ambiguous)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SbdbResponse {
+    #[serde(default)]
+    pub object: Option<SbdbObject>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub list: Option<Vec<SbdbMatch>>,
+}
+
+impl Spec for Sbdb {
+    const BASE_URL: &'static str = "https://ssd-api.jpl.nasa.gov/sbdb.api?";
+    type Params = SbdbParams;
+    type ResponseType = SbdbResponse;
+
+    fn validate(params: &SbdbParams) -> crate::error::Result<()> {
+        if params.sstr.trim().is_empty() {
+            return Err(Error::InvalidParameter {
+                name: "sstr",
+                reason: "must not be empty".to_owned(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Implement the client handler for SBDB, without the api.nasa.gov key
+impl ClientHandler<Sbdb> for Client<Sbdb> {
+    fn build_query_with(
+        config: &ClientConfig,
+        values: std::collections::HashMap<String, String>,
+    ) -> crate::error::Result<String> {
+        let url =
+            crate::query::build_url(&config.endpoint(<Sbdb as Spec>::BASE_URL), values, None)?;
+
+        log::debug!("Built query bound for: {}", url);
+
+        Ok(url)
+    }
+}
+
+impl Client<Sbdb> {
+    /// Resolve a search string to a single object
+    ///
+    /// Unknown and ambiguous searches are reported as [Error::InvalidParameter]
+    pub fn object(&self, sstr: &str) -> crate::error::Result<SbdbObject> {
+        let response = match self.query(&SbdbParams::search(sstr)) {
+            Ok(response) => response,
+            // several matches come back as 300 Multiple Choices
+            Err(Error::Status { status, body }) if status.as_u16() == 300 => {
+                serde_json::from_str(&body)?
+            }
+            Err(err) => return Err(err),
+        };
+        if let Some(object) = response.object {
+            return Ok(object);
+        }
+        let mut reason = response
+            .message
+            .unwrap_or_else(|| "no matching object".to_owned());
+        if let Some(list) = response.list {
+            let names: Vec<_> = list.into_iter().map(|m| m.name).collect();
+            reason.push_str(&format!(": {}", names.join(", ")));
+        }
+        Err(Error::InvalidParameter {
+            name: "sstr",
+            reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolves_designations() {
+        let client = crate::transport::test_client::<Sbdb>();
+        let object = client.object("2023 DW").unwrap();
+        assert_eq!(object.spkid, "54338723");
+        assert!(object.neo);
+        assert_eq!(object.orbit_class.unwrap().code, "ATE");
+
+        let url = <Client<Sbdb> as ClientHandler<_>>::build_query(
+            client.config(),
+            SbdbParams::search("2023 DW"),
+        )
+        .unwrap();
+        assert!(!url.contains("api_key"));
+    }
+
+    #[test]
+    fn reports_unknown_and_ambiguous() {
+        let client = crate::transport::test_client::<Sbdb>();
+        let err = client.object("2099 ZZ999").unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidParameter { reason, .. } if reason.contains("not found"))
+        );
+        let err = client.object("PK9").unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidParameter { reason, .. } if reason.contains("(2010 PK9)"))
+        );
+    }
+}
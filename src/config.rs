@@ -0,0 +1,310 @@
+use crate::error::{Error, Result};
+use crate::prelude::{Client, Spec};
+use crate::rate_limit::{QuotaTracker, RateLimit, RetryPolicy, ThrottlePolicy};
+use crate::transport::{HttpTransport, Transport};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Key used when neither an explicit key nor `NASA_API_KEY` is available
+pub const DEMO_KEY: &str = "DEMO_KEY";
+/// User agent sent when none is configured
+pub const DEFAULT_USER_AGENT: &str = concat!("nasa-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Settings shared by every request a client sends
+#[derive(Clone)]
+pub struct ClientConfig {
+    api_key: String,
+    base_url: Option<String>,
+    user_agent: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    throttle: ThrottlePolicy,
+    retry: RetryPolicy,
+    pub(crate) quota: QuotaTracker,
+}
+
+impl ClientConfig {
+    /// The API key attached to every request
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// The host override, if any
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    /// The user agent sent with every request
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Timeout for a whole request
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Timeout for establishing a connection
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// What happens once the last seen quota is exhausted
+    pub fn throttle(&self) -> ThrottlePolicy {
+        self.throttle
+    }
+
+    /// How 429 and 5xx responses are retried
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    /// The quota reported by the most recent response, if any
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.quota.get()
+    }
+
+    /// Resolve a spec's default URL against the configured host
+    ///
+    /// `https://api.nasa.gov/planetary/apod?` with a base url of
+    /// `http://localhost:8080` resolves to `http://localhost:8080/planetary/apod?`
+    pub fn endpoint(&self, default_url: &str) -> String {
+        let Some(base_url) = &self.base_url else {
+            return default_url.to_owned();
+        };
+        let path = default_url
+            .find("://")
+            .and_then(|scheme| {
+                let host = scheme + 3;
+                default_url[host..].find('/').map(|path| host + path)
+            })
+            .map_or("", |path| &default_url[path..]);
+        format!("{}{}", base_url.trim_end_matches('/'), path)
+    }
+}
+
+impl std::fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("api_key", &"<redacted>")
+            .field("base_url", &self.base_url)
+            .field("user_agent", &self.user_agent)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("throttle", &self.throttle)
+            .field("retry", &self.retry)
+            .field("rate_limit", &self.quota.get())
+            .finish()
+    }
+}
+
+/// Builder for [Client] (and `AsyncClient` with the `async` feature)
+pub struct ClientBuilder<S: Spec> {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    throttle: ThrottlePolicy,
+    retry: RetryPolicy,
+    transport: Option<Arc<dyn Transport>>,
+    #[cfg(feature = "async")]
+    async_transport: Option<Arc<dyn crate::transport::AsyncTransport>>,
+    _spec: std::marker::PhantomData<S>,
+}
+
+impl<S: Spec> Default for ClientBuilder<S> {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            base_url: None,
+            user_agent: None,
+            timeout: None,
+            connect_timeout: None,
+            throttle: ThrottlePolicy::default(),
+            retry: RetryPolicy::default(),
+            transport: None,
+            #[cfg(feature = "async")]
+            async_transport: None,
+            _spec: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<S: Spec> ClientBuilder<S> {
+    /// Use an explicit API key instead of `NASA_API_KEY` or `DEMO_KEY`
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Send requests to another host, e.g. a local mock server
+    ///
+    /// Only the scheme and host of `Spec::BASE_URL` are replaced; the endpoint path is kept
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Override the default `nasa-rs/<version>` user agent
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Timeout for a whole request, from connecting until the body is read
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for establishing a connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// What to do before sending once the last seen quota is exhausted (default: send anyway)
+    pub fn throttle(mut self, throttle: ThrottlePolicy) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// How 429 and 5xx responses are retried (default: no retries)
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Reuse an existing blocking reqwest client (and its connection pool)
+    ///
+    /// The user agent and timeouts configured on the builder are not applied to it
+    pub fn http_client(self, http: reqwest::blocking::Client) -> Self {
+        self.transport(HttpTransport::new(http))
+    }
+
+    /// Send requests through a custom [Transport], e.g. a mock or a
+    /// [Cassette](crate::transport::Cassette)
+    ///
+    /// The user agent and timeouts configured on the builder are not applied to it
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Reuse an existing async reqwest client (and its connection pool)
+    ///
+    /// The user agent and timeouts configured on the builder are not applied to it
+    #[cfg(feature = "async")]
+    pub fn async_http_client(self, http: reqwest::Client) -> Self {
+        self.async_transport(crate::transport::AsyncHttpTransport::new(http))
+    }
+
+    /// Send async requests through a custom [AsyncTransport](crate::transport::AsyncTransport)
+    ///
+    /// The user agent and timeouts configured on the builder are not applied to it
+    #[cfg(feature = "async")]
+    pub fn async_transport(
+        mut self,
+        transport: impl crate::transport::AsyncTransport + 'static,
+    ) -> Self {
+        self.async_transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Build a blocking [Client]
+    pub fn build(mut self) -> Result<Client<S>> {
+        let transport = self.transport.take();
+        let config = self.config()?;
+        let transport = match transport {
+            Some(transport) => transport,
+            None => {
+                let mut http =
+                    reqwest::blocking::Client::builder().user_agent(config.user_agent.clone());
+                if let Some(timeout) = config.timeout {
+                    http = http.timeout(timeout);
+                }
+                if let Some(timeout) = config.connect_timeout {
+                    http = http.connect_timeout(timeout);
+                }
+                Arc::new(HttpTransport::new(http.build()?))
+            }
+        };
+        Ok(Client::from_parts(config, transport))
+    }
+
+    /// Build a non-blocking [AsyncClient](crate::prelude::AsyncClient)
+    #[cfg(feature = "async")]
+    pub fn build_async(mut self) -> Result<crate::prelude::AsyncClient<S>> {
+        let transport = self.async_transport.take();
+        let config = self.config()?;
+        let transport = match transport {
+            Some(transport) => transport,
+            None => {
+                let mut http = reqwest::Client::builder().user_agent(config.user_agent.clone());
+                if let Some(timeout) = config.timeout {
+                    http = http.timeout(timeout);
+                }
+                if let Some(timeout) = config.connect_timeout {
+                    http = http.connect_timeout(timeout);
+                }
+                Arc::new(crate::transport::AsyncHttpTransport::new(http.build()?))
+            }
+        };
+        Ok(crate::prelude::AsyncClient::from_parts(config, transport))
+    }
+
+    fn config(&self) -> Result<ClientConfig> {
+        let api_key = match &self.api_key {
+            Some(key) if key.trim().is_empty() => return Err(Error::ApiKey),
+            Some(key) => key.clone(),
+            None => try_api_key_from_env().unwrap_or_else(|| {
+                log::warn!(
+                    "NASA_API_KEY not set or blank, falling back to {}",
+                    DEMO_KEY
+                );
+                DEMO_KEY.to_owned()
+            }),
+        };
+        Ok(ClientConfig {
+            api_key,
+            base_url: self.base_url.clone(),
+            user_agent: self
+                .user_agent
+                .clone()
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_owned()),
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            throttle: self.throttle,
+            retry: self.retry,
+            quota: QuotaTracker::default(),
+        })
+    }
+}
+
+/// Try and read an API key from the environment
+///
+/// A blank `NASA_API_KEY` (e.g. `NASA_API_KEY=` in `.env`) counts as unset
+pub(crate) fn try_api_key_from_env() -> Option<String> {
+    dotenv::dotenv().ok();
+    non_blank(std::env::var("NASA_API_KEY").ok())
+}
+
+fn non_blank(api_key: Option<String>) -> Option<String> {
+    api_key
+        .map(|key| key.trim().to_owned())
+        .filter(|key| !key.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn blank_env_keys_count_as_unset() {
+        assert_eq!(non_blank(None), None);
+        assert_eq!(non_blank(Some(String::new())), None);
+        assert_eq!(non_blank(Some("  \n".to_owned())), None);
+        assert_eq!(non_blank(Some(" abc ".to_owned())).as_deref(), Some("abc"));
+    }
+}
@@ -1,5 +1,34 @@
-#[derive(Debug)]
+/// Errors produced while building, sending or decoding a request
+#[derive(Debug, thiserror::Error)]
 pub enum Error {
-    SerializationError(serde_qs::Error),
-    ApiKeyError,
+    /// The API key passed to the builder is empty
+    #[error("API key is empty")]
+    ApiKey,
+    /// The request could not be sent, or its body could not be read
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    /// The API responded with a non-success status
+    #[error("API responded with {status}: {body}")]
+    Status {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    /// The API key has exhausted its quota (HTTP 429)
+    #[error("rate limit exceeded (limit: {limit:?}, remaining: {remaining:?})")]
+    RateLimited {
+        limit: Option<u32>,
+        remaining: Option<u32>,
+    },
+    /// The response body did not match the expected shape
+    #[error("failed to decode response: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// Reading or writing local files (recordings, downloads) failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// A query parameter was rejected before the request was sent
+    #[error("invalid parameter `{name}`: {reason}")]
+    InvalidParameter { name: &'static str, reason: String },
 }
+
+/// Result alias for fallible client operations
+pub type Result<T> = std::result::Result<T, Error>;
@@ -1,7 +1,7 @@
 //! nasa-rs provides client wrappers for
 //! [NASA's open APIs](https://api.nasa.gov/)
 //! > Example using the Astronomy Picture of the Day (APOD) API
-//! ```rust
+//! ```no_run
 //! use nasa_rs::{
 //!    prelude::*,
 //!    Apod,
@@ -17,11 +17,20 @@
 //! }
 //!
 //! ```
-#![feature(associated_type_defaults, associated_type_bounds)]
+//!
+//! With the `async` feature enabled, [prelude::AsyncClient] exposes the same
+//! `query`/`query_with` interface through [prelude::AsyncClientHandler] for use
+//! inside an async runtime.
+#![feature(associated_type_defaults)]
 #![allow(unused_parens)]
 
+// lets `#[derive(QueryValues)]` refer to `::nasa_rs` from inside the crate
+extern crate self as nasa_rs;
+
 /// Client implementations
 pub mod clients;
+/// Client configuration and builder
+pub mod config;
 /// Error types
 pub mod error;
 /// Common Parameters
@@ -29,18 +38,30 @@ pub mod params;
 /// Common interfaces
 pub mod prelude;
 /// Interfaces for Query values
-pub(crate) mod query;
+pub mod query;
+/// Rate-limit tracking and retry policies
+pub mod rate_limit;
+/// Pluggable HTTP transports, including record/replay
+pub mod transport;
 
 /// Client handler for the Astronomy Picture of the Day (APOD) API
 pub type Apod = prelude::Client<clients::Apod>;
 /// Client handler for the Near Earth Object Web Service (NeoWs) API
 pub type NeoWs = prelude::Client<clients::Neo>;
+/// Client handler for the Earth (Landsat imagery) API
+pub type EarthImagery = prelude::Client<clients::EarthImagery>;
+/// Client handler for the Earth Assets (Landsat scene search) API
+pub type EarthAssets = prelude::Client<clients::EarthAssets>;
 /// Client handler for the Geomagnetic Storm (GST) API
 pub type Gst = prelude::Client<clients::Gst>;
-/// Client handler for the CME Analysis (CME) API
+/// Client handler for the Coronal Mass Ejection (CME) API
+pub type Cme = prelude::Client<clients::Cme>;
+/// Client handler for the CME Analysis API
 pub type CmeAnalysis = prelude::Client<clients::CmeAnalysis>;
 /// Client handler for the Solar Energetic Particle (SEP) API
 pub type Flr = prelude::Client<clients::Flr>;
+/// Client handler for the DONKI Notifications API
+pub type Notifications = prelude::Client<clients::Notifications>;
 /// Client handler for the Solar Energetic Particle (SEP) API
 pub type Sep = prelude::Client<clients::Sep>;
 
@@ -53,15 +74,77 @@ pub(crate) mod macros {
             impl $crate::prelude::Spec for $client {
                 const BASE_URL: &'static str = $base_url;
                 type Params = $params_type;
+            }
+
+            impl ClientHandler<$client> for Client<$client> {}
+        };
+        ($client:ident, $base_url:expr, $params_type:ty, $response_type:ty) => {
+            pub struct $client;
+
+            impl $crate::prelude::Spec for $client {
+                const BASE_URL: &'static str = $base_url;
+                type Params = $params_type;
+                type ResponseType = $response_type;
 
-                fn parse_response(res: reqwest::blocking::Response) -> Self::ResponseType {
-                    res.json().unwrap()
+                fn validate(params: &$params_type) -> $crate::error::Result<()> {
+                    params.validate()
                 }
             }
 
             impl ClientHandler<$client> for Client<$client> {}
         };
     }
+
+    /// An enum sent as a query value, with one wire name per variant shared by
+    /// serde, `as_str`, `Display` and `FromStr`
+    #[macro_export]
+    macro_rules! query_enum {
+        (
+            $(#[$meta:meta])*
+            $vis:vis enum $name:ident ($param:literal) {
+                $($(#[$variant_meta:meta])* $variant:ident = $wire:literal,)*
+            }
+        ) => {
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+            $vis enum $name {
+                $($(#[$variant_meta])* #[serde(rename = $wire)] $variant,)*
+            }
+
+            impl $name {
+                /// Every variant, in declaration order
+                pub const VARIANTS: &'static [Self] = &[$(Self::$variant),*];
+
+                /// The value sent on the wire
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $wire,)*
+                    }
+                }
+            }
+
+            impl std::fmt::Display for $name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl std::str::FromStr for $name {
+                type Err = $crate::error::Error;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    Self::VARIANTS
+                        .iter()
+                        .copied()
+                        .find(|variant| variant.as_str() == value)
+                        .ok_or_else(|| $crate::error::Error::InvalidParameter {
+                            name: $param,
+                            reason: format!("unknown value {value:?}"),
+                        })
+                }
+            }
+        };
+    }
 }
 
 #[cfg(test)]
@@ -71,11 +154,31 @@ mod test {
 
     #[test]
     fn query_with() {
-        let client = Client::<Apod>::default();
+        let client = crate::transport::test_client::<Apod>();
         let response = client
             .query_with(vec![("date", "2019-01-01"), ("hd", "true")])
             .map_err(|e| log::error!("{}", e))
             .unwrap();
         log::debug!("{}", serde_json::to_string_pretty(&response).unwrap());
     }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn query_async() {
+        use crate::clients::{CmeAnalysis, Neo, NeoParams};
+        use crate::transport::test_async_client;
+
+        let apod = test_async_client::<Apod>()
+            .query_with(vec![("date", "2019-01-01"), ("hd", "true")])
+            .await;
+        assert!(apod.is_ok());
+        let neo = test_async_client::<Neo>()
+            .query(&NeoParams::default())
+            .await;
+        assert!(neo.is_ok());
+        let cme = test_async_client::<CmeAnalysis>()
+            .query(&Default::default())
+            .await;
+        assert!(cme.is_ok());
+    }
 }
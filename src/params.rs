@@ -1,3 +1,6 @@
+use crate::error::{Error, Result};
+use crate::query::QueryValues;
+use chrono::NaiveDate;
 use serde::Serialize;
 
 pub use crate::clients::{
@@ -7,38 +10,132 @@ pub use crate::clients::{
         // ...
         // flr,gst,hss,mpc,rbe,sep,wsa all use ParamsCommon
         ips::IpsParams,
+        notifications::NotificationsParams,
     },
-    earth::imagery::ParamsEarthImagery,
+    earth::{assets::ParamsEarthAssets, imagery::ParamsEarthImagery},
     neo::NeoParams,
 };
 
-#[derive(Debug, Clone, Serialize)]
+/// Days before the current UTC date that DONKI starts searching when
+/// `startDate` is omitted
+pub const DONKI_DEFAULT_WINDOW_DAYS: i64 = 30;
+
+/// The dates DONKI searches for an optional `startDate`/`endDate` pair
+pub(crate) fn donki_window(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> (NaiveDate, NaiveDate) {
+    let today = chrono::Utc::now().date_naive();
+    (
+        start_date.unwrap_or(today - chrono::Duration::days(DONKI_DEFAULT_WINDOW_DAYS)),
+        end_date.unwrap_or(today),
+    )
+}
+
+/// Reject a date range that ends before it starts, once defaults are filled in
+pub(crate) fn validate_donki_window(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<()> {
+    let (start, end) = donki_window(start_date, end_date);
+    if start > end {
+        return Err(Error::InvalidParameter {
+            name: "startDate",
+            reason: format!("{} is after endDate {}", start, end),
+        });
+    }
+    Ok(())
+}
+
+/// Date range shared by most DONKI endpoints
+///
+/// Without dates the API searches the last
+/// [DONKI_DEFAULT_WINDOW_DAYS] days; see [ParamsCommon::window]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, QueryValues)]
 #[serde(rename_all = "camelCase")]
 pub struct ParamsCommon {
     /// default to 30 days prior to current UTC date
-    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<NaiveDate>,
     /// default to current UTC date
-    end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<NaiveDate>,
 }
 
-impl Default for ParamsCommon {
-    fn default() -> Self {
+impl ParamsCommon {
+    /// Events from `start` to `end`, inclusive
+    pub fn range(start: NaiveDate, end: NaiveDate) -> Self {
         Self {
-            start_date: None,
-            end_date: None,
+            start_date: Some(start),
+            end_date: Some(end),
         }
     }
-}
 
-impl crate::query::QueryValues for ParamsCommon {
-    fn values(&self) -> std::collections::HashMap<String, String> {
-        let mut map = std::collections::HashMap::new();
-        if let Some(start_date) = &self.start_date {
-            map.insert("startDate".to_string(), start_date.to_owned());
-        }
-        if let Some(end_date) = &self.end_date {
-            map.insert("endDate".to_string(), end_date.to_owned());
+    /// Events from `start` to today
+    pub fn since(start: NaiveDate) -> Self {
+        Self {
+            start_date: Some(start),
+            ..Default::default()
         }
-        map
+    }
+
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        self.start_date
+    }
+
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        self.end_date
+    }
+
+    /// The dates the API will search, with defaults filled in
+    pub fn window(&self) -> (NaiveDate, NaiveDate) {
+        donki_window(self.start_date, self.end_date)
+    }
+
+    /// Reject a range that ends before it starts
+    pub fn validate(&self) -> Result<()> {
+        validate_donki_window(self.start_date, self.end_date)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::ymd;
+    use crate::{prelude::ClientHandler, query::QueryValues};
+
+    #[test]
+    fn default_window_is_the_last_30_days() {
+        let today = chrono::Utc::now().date_naive();
+        let (start, end) = ParamsCommon::default().window();
+        assert_eq!(end, today);
+        assert_eq!((end - start).num_days(), DONKI_DEFAULT_WINDOW_DAYS);
+
+        let params = ParamsCommon::range(ymd(2017, 9, 1), ymd(2017, 9, 30));
+        assert_eq!(params.window(), (ymd(2017, 9, 1), ymd(2017, 9, 30)));
+        assert_eq!(ParamsCommon::since(ymd(2017, 9, 1)).window().1, today);
+        assert_eq!(params.values().unwrap()["startDate"], "2017-09-01");
+    }
+
+    #[test]
+    fn rejects_reversed_ranges() {
+        let reversed = ParamsCommon::range(ymd(2024, 5, 12), ymd(2024, 5, 8));
+        assert!(matches!(
+            reversed.validate(),
+            Err(Error::InvalidParameter {
+                name: "startDate",
+                ..
+            })
+        ));
+        let future = chrono::Utc::now().date_naive() + chrono::Duration::days(1);
+        assert!(ParamsCommon::since(future).validate().is_err());
+        assert!(ParamsCommon::default().validate().is_ok());
+
+        // rejected before the (unrecorded) request is sent
+        let client = crate::transport::test_client::<crate::clients::Gst>();
+        assert!(matches!(
+            client.query(&reversed),
+            Err(Error::InvalidParameter { .. })
+        ));
     }
 }
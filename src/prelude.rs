@@ -1,108 +1,388 @@
+use crate::error::{Error, Result};
 use crate::query::QueryValues;
+use std::sync::Arc;
+
+pub use crate::config::{ClientBuilder, ClientConfig};
+use crate::rate_limit::header_u32;
+pub use crate::rate_limit::{RateLimit, RetryPolicy, ThrottlePolicy};
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+use crate::transport::{Request, Response, Transport};
 
 /// A Nasa API Spec
 pub trait Spec {
-    /// The base url for the API
+    /// The default url for the API; the host can be overridden per client with
+    /// [ClientBuilder::base_url]
     const BASE_URL: &'static str;
     /// Query parameters for the API: Usually an enum
-    type Params: serde::Serialize + Clone + Default;
+    type Params: QueryValues + Clone + Default;
     /// Response type for the API: Usually a serde_json::Value
-    type ResponseType = serde_json::Value;
-    /// Error type for the API
-    type Error = Box<dyn std::error::Error>;
+    type ResponseType: serde::de::DeserializeOwned = serde_json::Value;
+
+    /// Reject parameters the API would refuse, before anything is sent
+    fn validate(_params: &Self::Params) -> Result<()> {
+        Ok(())
+    }
 
-    /// reqwest::Response -> Self::ResponseType
-    fn parse_response(res: reqwest::blocking::Response) -> Self::ResponseType;
+    /// Response -> Self::ResponseType
+    ///
+    /// Only called for successful responses; non-success statuses are mapped
+    /// onto [Error] before the body reaches the spec
+    fn parse_response(res: Response) -> Result<Self::ResponseType> {
+        res.json()
+    }
+
+    /// Response to a request for `url` -> Self::ResponseType
+    ///
+    /// What `query` and `query_with` call; specs whose response shape depends on
+    /// the endpoint override this, the rest [Spec::parse_response]
+    fn parse_response_for(_url: &str, res: Response) -> Result<Self::ResponseType> {
+        Self::parse_response(res)
+    }
 }
 
 /// Core client functionality
 pub trait ClientHandler<S: Spec>
 where
-    Self: Default,
+    Self: Default + AsRef<Client<S>>,
 {
-    /// Build the query string
-    fn build_query(params: S::Params) -> Result<String, crate::error::Error> {
-        let mut url = S::BASE_URL.to_owned();
-        url.push_str(
-            &serde_qs::to_string(&params)
-                .map_err(|e| crate::error::Error::SerializationError(e))?,
-        );
-
-        if let Some(key) = crate::prelude::try_api_key_from_env() {
-            url.push_str(&format!("&api_key={}", key));
-        } else {
-            log::error!("No API key found in environment");
-            return Err(crate::error::Error::ApiKeyError);
-        }
+    /// Build the URL for `params`
+    fn build_query(config: &ClientConfig, params: S::Params) -> Result<String> {
+        Self::build_query_with(config, params.values()?)
+    }
+
+    /// Build the URL for raw query values
+    ///
+    /// Both `query` and `query_with` end up here, so the same values give the
+    /// same URL: sorted, percent-encoded and carrying the API key
+    fn build_query_with(
+        config: &ClientConfig,
+        values: std::collections::HashMap<String, String>,
+    ) -> Result<String> {
+        let url = crate::query::build_url(
+            &config.endpoint(S::BASE_URL),
+            values,
+            Some(config.api_key()),
+        )?;
 
         log::debug!("Built query bound for: {}", url);
 
-        return Ok(url);
+        Ok(url)
     }
 
     /// Query the API
-    fn query(&self, params: &S::Params) -> Result<S::ResponseType, S::Error> {
-        let url = Self::build_query(params.clone()).unwrap();
-        let response = reqwest::blocking::Client::new()
-            .get(&url)
-            .send()
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
-            .unwrap();
-        let response = S::parse_response(response);
-        Ok(response)
+    fn query(&self, params: &S::Params) -> Result<S::ResponseType> {
+        S::validate(params)?;
+        let client = self.as_ref();
+        let url = Self::build_query(client.config(), params.clone())?;
+        S::parse_response_for(&url, client.send(&url)?)
     }
 
     /// Query with generic params
-    fn query_with(&self, params: impl QueryValues) -> Result<S::ResponseType, S::Error> {
-        let values = params.values();
-        let query = map_to_query(values);
-        let url = S::BASE_URL.to_owned() + "?" + &query;
-        let response = reqwest::blocking::Client::new()
-            .get(&url)
-            .send()
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
-            .unwrap();
-        let response = S::parse_response(response);
-        Ok(response)
+    fn query_with(&self, params: impl QueryValues) -> Result<S::ResponseType> {
+        let client = self.as_ref();
+        let url = Self::build_query_with(client.config(), params.values()?)?;
+        S::parse_response_for(&url, client.send(&url)?)
     }
 }
 
 /// An agnostic client that can be used to wrap any API
+///
+/// Cloning is cheap and clones share the underlying transport (and connection pool)
 pub struct Client<S: Spec> {
+    config: Arc<ClientConfig>,
+    transport: Arc<dyn Transport>,
     _spec: std::marker::PhantomData<S>,
 }
 
+impl<S: Spec> Client<S> {
+    /// Configure a client: API key, host, timeouts and user agent
+    pub fn builder() -> ClientBuilder<S> {
+        ClientBuilder::default()
+    }
+
+    /// The settings this client was built with
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// The quota reported by the most recent response, if any
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.config.rate_limit()
+    }
+
+    pub(crate) fn from_parts(config: ClientConfig, transport: Arc<dyn Transport>) -> Self {
+        Self {
+            config: Arc::new(config),
+            transport,
+            _spec: std::marker::PhantomData,
+        }
+    }
+
+    /// A client for another spec sharing this client's settings and transport
+    pub(crate) fn with_spec<T: Spec>(&self) -> Client<T> {
+        Client {
+            config: self.config.clone(),
+            transport: self.transport.clone(),
+            _spec: std::marker::PhantomData,
+        }
+    }
+
+    /// GET a url outside the API (e.g. linked media) through the client's transport,
+    /// without touching the API quota
+    pub(crate) fn fetch(&self, url: &str) -> Result<Response> {
+        error_for_status(self.transport.send(&Request::get(url))?)
+    }
+
+    /// GET `url`, applying the throttle and retry policies
+    pub(crate) fn send(&self, url: &str) -> Result<Response> {
+        let request = Request::get(url);
+        let mut attempt = 0;
+        loop {
+            if let Some(wait) = self.config.quota.check(self.config.throttle())? {
+                std::thread::sleep(wait);
+            }
+            let response = self.transport.send(&request)?;
+            self.config.quota.record(&response.headers);
+            let retry =
+                self.config
+                    .retry_policy()
+                    .retry_after(attempt, response.status, &response.headers);
+            match retry {
+                Some(wait) => {
+                    log::warn!("{} from {}, retrying in {:?}", response.status, url, wait);
+                    std::thread::sleep(wait);
+                    attempt += 1;
+                }
+                None => return error_for_status(response),
+            }
+        }
+    }
+}
+
 impl<S> Default for Client<S>
 where
     S: Spec,
 {
+    /// Key from `NASA_API_KEY` (or `DEMO_KEY`), default host and timeouts
     fn default() -> Self {
+        Self::builder()
+            .build()
+            .expect("default client configuration is valid")
+    }
+}
+
+impl<S: Spec> Clone for Client<S> {
+    fn clone(&self) -> Self {
         Self {
+            config: self.config.clone(),
+            transport: self.transport.clone(),
             _spec: std::marker::PhantomData,
         }
     }
 }
 
-/// Build a query string from a hashmap
-pub(crate) fn map_to_query(params: std::collections::HashMap<String, String>) -> String {
-    let mut url = String::new();
-    let size = params.len();
-    for (i, (key, value)) in params.into_iter().enumerate() {
-        url.push_str(&key);
-        url.push_str("=");
-        url.push_str(&value);
-        if i < size - 1 {
-            url.push_str("&");
+impl<S: Spec> AsRef<Client<S>> for Client<S> {
+    fn as_ref(&self) -> &Client<S> {
+        self
+    }
+}
+
+/// An agnostic non-blocking client that can be used to wrap any API
+///
+/// Cloning is cheap and clones share the underlying transport (and connection pool)
+#[cfg(feature = "async")]
+pub struct AsyncClient<S: Spec> {
+    config: Arc<ClientConfig>,
+    transport: Arc<dyn AsyncTransport>,
+    _spec: std::marker::PhantomData<S>,
+}
+
+#[cfg(feature = "async")]
+impl<S: Spec> AsyncClient<S> {
+    /// Configure a client: API key, host, timeouts and user agent
+    pub fn builder() -> ClientBuilder<S> {
+        ClientBuilder::default()
+    }
+
+    /// The settings this client was built with
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// The quota reported by the most recent response, if any
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.config.rate_limit()
+    }
+
+    pub(crate) fn from_parts(config: ClientConfig, transport: Arc<dyn AsyncTransport>) -> Self {
+        Self {
+            config: Arc::new(config),
+            transport,
+            _spec: std::marker::PhantomData,
+        }
+    }
+
+    /// GET `url`, applying the throttle and retry policies
+    async fn send(&self, url: &str) -> Result<Response> {
+        let request = Request::get(url);
+        let mut attempt = 0;
+        loop {
+            if let Some(wait) = self.config.quota.check(self.config.throttle())? {
+                tokio::time::sleep(wait).await;
+            }
+            let response = self.transport.send(&request).await?;
+            self.config.quota.record(&response.headers);
+            let retry =
+                self.config
+                    .retry_policy()
+                    .retry_after(attempt, response.status, &response.headers);
+            match retry {
+                Some(wait) => {
+                    log::warn!("{} from {}, retrying in {:?}", response.status, url, wait);
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                None => return error_for_status(response),
+            }
         }
     }
-    url
 }
 
-/// Try and read an API key from the environment
-pub(crate) fn try_api_key_from_env() -> Option<String> {
-    dotenv::dotenv().ok();
-    match std::env::var("NASA_API_KEY") {
-        Ok(key) => Some(key),
-        Err(_) => None,
+#[cfg(feature = "async")]
+impl<S> Default for AsyncClient<S>
+where
+    S: Spec,
+{
+    /// Key from `NASA_API_KEY` (or `DEMO_KEY`), default host and timeouts
+    fn default() -> Self {
+        Self::builder()
+            .build_async()
+            .expect("default client configuration is valid")
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S: Spec> Clone for AsyncClient<S> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            transport: self.transport.clone(),
+            _spec: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Core non-blocking client functionality
+///
+/// Mirrors [ClientHandler], reusing its `build_query` and `build_query_with` so both clients hit the same URLs
+#[cfg(feature = "async")]
+pub trait AsyncClientHandler<S: Spec>
+where
+    Self: Default,
+{
+    /// Query the API
+    fn query(
+        &self,
+        params: &S::Params,
+    ) -> impl std::future::Future<Output = Result<S::ResponseType>> + Send;
+
+    /// Query with generic params
+    fn query_with(
+        &self,
+        params: impl QueryValues + Send,
+    ) -> impl std::future::Future<Output = Result<S::ResponseType>> + Send;
+}
+
+#[cfg(feature = "async")]
+impl<S> AsyncClientHandler<S> for AsyncClient<S>
+where
+    S: Spec + Sync,
+    S::Params: Sync,
+    Client<S>: ClientHandler<S>,
+{
+    async fn query(&self, params: &S::Params) -> Result<S::ResponseType> {
+        S::validate(params)?;
+        let url = <Client<S> as ClientHandler<S>>::build_query(&self.config, params.clone())?;
+        S::parse_response_for(&url, self.send(&url).await?)
+    }
+
+    async fn query_with(&self, params: impl QueryValues + Send) -> Result<S::ResponseType> {
+        let url =
+            <Client<S> as ClientHandler<S>>::build_query_with(&self.config, params.values()?)?;
+        S::parse_response_for(&url, self.send(&url).await?)
+    }
+}
+
+/// Pass successful responses through, mapping anything else onto [Error]
+pub(crate) fn error_for_status(res: Response) -> Result<Response> {
+    if res.status.is_success() {
+        return Ok(res);
+    }
+    Err(status_error(
+        res.status,
+        &res.headers,
+        res.text().into_owned(),
+    ))
+}
+
+/// Build the [Error] for a non-success status
+pub(crate) fn status_error(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+    body: String,
+) -> Error {
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Error::RateLimited {
+            limit: header_u32(headers, "X-RateLimit-Limit"),
+            remaining: header_u32(headers, "X-RateLimit-Remaining"),
+        };
+    }
+    Error::Status { status, body }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn status_error_maps_rate_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", HeaderValue::from_static("30"));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("0"));
+        let err = status_error(StatusCode::TOO_MANY_REQUESTS, &headers, String::new());
+        assert!(matches!(
+            err,
+            Error::RateLimited {
+                limit: Some(30),
+                remaining: Some(0)
+            }
+        ));
+
+        let err = status_error(StatusCode::FORBIDDEN, &HeaderMap::new(), "bad key".into());
+        assert!(matches!(err, Error::Status { status, .. } if status == StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn builder_overrides_host_and_key() {
+        let client = Client::<crate::clients::Apod>::builder()
+            .api_key("test-key")
+            .base_url("http://127.0.0.1:8080/")
+            .build()
+            .unwrap();
+        let url = <Client<crate::clients::Apod> as ClientHandler<_>>::build_query(
+            client.config(),
+            crate::clients::ApodParams::default(),
+        )
+        .unwrap();
+        assert!(url.starts_with("http://127.0.0.1:8080/planetary/apod?"));
+        assert_eq!(url, "http://127.0.0.1:8080/planetary/apod?api_key=test-key");
+
+        let err = Client::<crate::clients::Apod>::builder()
+            .api_key(" ")
+            .build();
+        assert!(matches!(err, Err(Error::ApiKey)));
     }
 }
@@ -1,20 +1,266 @@
+/// `#[derive(QueryValues)]`, keyed by the type's serde attributes
+pub use nasa_rs_derive::QueryValues;
+
 /// Enum --> K/V pairs
 pub trait QueryValues {
-    fn values(&self) -> std::collections::HashMap<String, String>;
+    /// The parameters to send; [Error::InvalidParameter](crate::error::Error::InvalidParameter)
+    /// names a value that cannot be written
+    fn values(&self) -> crate::error::Result<std::collections::HashMap<String, String>>;
 }
 
 impl QueryValues for std::collections::HashMap<String, String> {
-    fn values(&self) -> std::collections::HashMap<String, String> {
-        self.clone()
+    fn values(&self) -> crate::error::Result<std::collections::HashMap<String, String>> {
+        Ok(self.clone())
     }
 }
 
 impl QueryValues for Vec<(&str, &str)> {
-    fn values(&self) -> std::collections::HashMap<String, String> {
+    fn values(&self) -> crate::error::Result<std::collections::HashMap<String, String>> {
         let mut map = std::collections::HashMap::new();
         for (key, value) in self {
             map.insert(key.to_string(), value.to_string());
         }
-        map
+        Ok(map)
+    }
+}
+
+/// The value of parameter `name` as sent in a query string, by its serde representation
+///
+/// Strings (including enum variant names) are used as is, numbers and bools
+/// are formatted and lists are joined with `,`; `None` gives `None`. A value
+/// serde cannot write (e.g. a map with non-string keys) is an
+/// [Error::InvalidParameter](crate::error::Error::InvalidParameter)
+pub fn to_value<T: serde::Serialize + ?Sized>(
+    name: &'static str,
+    value: &T,
+) -> crate::error::Result<Option<String>> {
+    fn format(value: serde_json::Value) -> Option<String> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(value) => Some(value),
+            serde_json::Value::Number(number) => {
+                Some(match (number.as_u64(), number.as_i64(), number.as_f64()) {
+                    (Some(value), _, _) => value.to_string(),
+                    (_, Some(value), _) => value.to_string(),
+                    (_, _, Some(value)) => value.to_string(),
+                    _ => number.to_string(),
+                })
+            }
+            serde_json::Value::Array(values) => Some(
+                values
+                    .into_iter()
+                    .filter_map(format)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            value => Some(value.to_string()),
+        }
+    }
+    let value =
+        serde_json::to_value(value).map_err(|err| crate::error::Error::InvalidParameter {
+            name,
+            reason: err.to_string(),
+        })?;
+    Ok(format(value))
+}
+
+/// `endpoint` with `values` as its query string
+///
+/// Parameters are sorted by name and escaped with [encode], so the same values
+/// always give the same URL; `api_key` goes last when given
+pub(crate) fn build_url(
+    endpoint: &str,
+    values: std::collections::HashMap<String, String>,
+    api_key: Option<&str>,
+) -> crate::error::Result<String> {
+    let mut url =
+        reqwest::Url::parse(endpoint).map_err(|err| crate::error::Error::InvalidParameter {
+            name: "base_url",
+            reason: format!("{endpoint:?}: {err}"),
+        })?;
+    let sorted: std::collections::BTreeMap<_, _> = values.into_iter().collect();
+    let mut query: Vec<String> = sorted
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect();
+    if let Some(api_key) = api_key {
+        query.push(format!("api_key={}", encode(api_key)));
+    }
+    if query.is_empty() {
+        url.set_query(None);
+    } else {
+        url.set_query(Some(&query.join("&")));
+    }
+    Ok(url.into())
+}
+
+/// Percent-encode a query key or value
+///
+/// Everything but ASCII letters, digits and `*-._` is escaped, spaces as `%20`:
+/// the APIs take values such as `STEREO%20A` and do not all read `+` as a space
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize, QueryValues)]
+    #[serde(rename_all = "camelCase")]
+    struct Params {
+        start_date: Option<chrono::NaiveDate>,
+        #[serde(rename = "type")]
+        kind: Option<crate::clients::NotificationType>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        ids: Vec<u32>,
+        #[allow(dead_code)]
+        #[serde(skip)]
+        internal: bool,
+        #[query(with = "yes_no")]
+        detailed: Option<bool>,
+        #[serde(flatten)]
+        location: Location,
+    }
+
+    #[derive(Serialize, QueryValues)]
+    struct Location {
+        lat: f64,
+        lon: f64,
+    }
+
+    #[derive(Serialize, QueryValues)]
+    #[serde(rename_all = "lowercase", rename_all_fields = "SCREAMING_SNAKE_CASE")]
+    enum Search {
+        ByName {
+            full_name: String,
+        },
+        #[allow(dead_code)]
+        Everything,
+    }
+
+    #[derive(Serialize, QueryValues)]
+    #[serde(rename_all = "camelCase")]
+    struct Raw {
+        r#type: &'static str,
+        r#in_range: bool,
+    }
+
+    #[derive(Serialize, QueryValues)]
+    struct Filters {
+        by_id: std::collections::HashMap<(u32, u32), bool>,
+    }
+
+    fn yes_no(value: &bool) -> String {
+        if *value { "Y" } else { "N" }.to_owned()
+    }
+
+    #[test]
+    fn derives_values_from_serde_attributes() {
+        let params = Params {
+            start_date: chrono::NaiveDate::from_ymd_opt(2024, 5, 8),
+            kind: Some(crate::clients::NotificationType::Report),
+            ids: vec![1, 2],
+            internal: true,
+            detailed: Some(false),
+            location: Location {
+                lat: 29.78,
+                lon: -95.0,
+            },
+        };
+        let values = params.values().unwrap();
+        let mut keys: Vec<_> = values.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["detailed", "ids", "lat", "lon", "startDate", "type"]);
+        assert_eq!(values["type"], "report");
+        assert_eq!(values["startDate"], "2024-05-08");
+        assert_eq!(values["ids"], "1,2");
+        assert_eq!(values["detailed"], "N");
+        assert_eq!(values["lat"], "29.78");
+        assert_eq!(values["lon"], "-95");
+        assert!(!Params {
+            ids: vec![],
+            ..params
+        }
+        .values()
+        .unwrap()
+        .contains_key("ids"));
+
+        let search = Search::ByName {
+            full_name: "Apophis".to_owned(),
+        };
+        assert_eq!(search.values().unwrap()["FULL_NAME"], "Apophis");
+        assert!(Search::Everything.values().unwrap().is_empty());
+        assert_eq!(
+            to_value("location", &crate::clients::IpsLocation::STEREO_A).unwrap(),
+            Some("STEREO A".to_owned())
+        );
+
+        let raw = Raw {
+            r#type: "FLR",
+            r#in_range: true,
+        }
+        .values()
+        .unwrap();
+        assert_eq!(raw["type"], "FLR");
+        assert_eq!(raw["inRange"], "true");
+        assert_eq!(
+            serde_json::to_value(Raw {
+                r#type: "FLR",
+                r#in_range: true,
+            })
+            .unwrap()["type"],
+            "FLR"
+        );
+
+        // serde can't write maps with non-string keys; that's an error, not a panic
+        let filters = Filters {
+            by_id: [((1, 2), true)].into_iter().collect(),
+        };
+        assert!(matches!(
+            filters.values(),
+            Err(crate::error::Error::InvalidParameter { name: "by_id", .. })
+        ));
+    }
+
+    #[test]
+    fn builds_sorted_escaped_urls() {
+        let values = vec![("location", "STEREO A"), ("catalog", "ALL"), ("q", "a+b&c")]
+            .values()
+            .unwrap();
+        assert_eq!(
+            build_url("https://api.nasa.gov/DONKI/IPS", values, Some("DEMO_KEY")).unwrap(),
+            "https://api.nasa.gov/DONKI/IPS?catalog=ALL&location=STEREO%20A&q=a%2Bb%26c&api_key=DEMO_KEY"
+        );
+        // no stray `?` or `&`, whether or not the endpoint ends in `?`
+        assert_eq!(
+            build_url(
+                "https://api.nasa.gov/DONKI/CME",
+                Default::default(),
+                Some("k")
+            )
+            .unwrap(),
+            "https://api.nasa.gov/DONKI/CME?api_key=k"
+        );
+        assert_eq!(
+            build_url(
+                "https://api.nasa.gov/planetary/apod?",
+                Default::default(),
+                None
+            )
+            .unwrap(),
+            "https://api.nasa.gov/planetary/apod"
+        );
+        assert!(build_url("not a url", Default::default(), None).is_err());
     }
 }
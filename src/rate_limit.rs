@@ -0,0 +1,226 @@
+use crate::error::{Error, Result};
+use reqwest::header::HeaderMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Quota reported by the API through the `X-RateLimit-*` headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per window (`X-RateLimit-Limit`)
+    pub limit: u32,
+    /// Requests left in the current window (`X-RateLimit-Remaining`)
+    pub remaining: u32,
+    /// When the headers were received
+    pub observed_at: Instant,
+}
+
+impl RateLimit {
+    /// Read the quota from response headers, if the API sent it
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Some(Self {
+            limit: header_u32(headers, "X-RateLimit-Limit")?,
+            remaining: header_u32(headers, "X-RateLimit-Remaining")?,
+            observed_at: Instant::now(),
+        })
+    }
+
+    /// Whether the next request would exceed the quota
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// What a client does before sending once the last seen quota is exhausted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThrottlePolicy {
+    /// Send anyway and let the API answer with 429
+    #[default]
+    Disabled,
+    /// Fail with [Error::RateLimited] without sending
+    Fail,
+    /// Block until `window` has passed since the quota ran out.
+    /// api.nasa.gov uses a rolling one hour window
+    Block { window: Duration },
+}
+
+impl ThrottlePolicy {
+    /// Block for api.nasa.gov's rolling one hour window
+    pub fn block() -> Self {
+        Self::Block {
+            window: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// How failed requests (429 and 5xx) are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for any single delay
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each retry
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    /// No retries
+    fn default() -> Self {
+        Self::exponential(0)
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_retries` times, starting at 500ms and doubling up to 30s
+    pub fn exponential(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+
+    /// Delay before retry number `attempt` (starting at 0)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powf(attempt as f64);
+        // clamp before building the Duration, which panics on overflow
+        Duration::try_from_secs_f64(self.initial_backoff.as_secs_f64() * factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    /// Delay before retrying a response with `status`, or `None` if it shouldn't be retried
+    pub(crate) fn retry_after(
+        &self,
+        attempt: u32,
+        status: reqwest::StatusCode,
+        headers: &HeaderMap,
+    ) -> Option<Duration> {
+        let retryable =
+            status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+        if !retryable || attempt >= self.max_retries {
+            return None;
+        }
+        let requested = header_u32(headers, "Retry-After")
+            .map(|secs| Duration::from_secs(secs as u64).min(self.max_backoff));
+        Some(requested.unwrap_or_else(|| self.backoff(attempt)))
+    }
+}
+
+/// Last seen quota, shared between clones of a client
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QuotaTracker {
+    last: Arc<Mutex<Option<RateLimit>>>,
+}
+
+impl QuotaTracker {
+    pub(crate) fn get(&self) -> Option<RateLimit> {
+        *self.last.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn record(&self, headers: &HeaderMap) {
+        if let Some(rate_limit) = RateLimit::from_headers(headers) {
+            log::debug!(
+                "Rate limit: {}/{} remaining",
+                rate_limit.remaining,
+                rate_limit.limit
+            );
+            *self.last.lock().unwrap_or_else(|e| e.into_inner()) = Some(rate_limit);
+        }
+    }
+
+    /// Apply `policy` before sending: how long to wait first, or an error if the
+    /// request must not be sent
+    pub(crate) fn check(&self, policy: ThrottlePolicy) -> Result<Option<Duration>> {
+        let Some(rate_limit) = self.get().filter(RateLimit::is_exhausted) else {
+            return Ok(None);
+        };
+        match policy {
+            ThrottlePolicy::Disabled => Ok(None),
+            ThrottlePolicy::Fail => Err(Error::RateLimited {
+                limit: Some(rate_limit.limit),
+                remaining: Some(rate_limit.remaining),
+            }),
+            ThrottlePolicy::Block { window } => {
+                // the quota is unknown again once the window has passed
+                *self.last.lock().unwrap_or_else(|e| e.into_inner()) = None;
+                let wait = window.saturating_sub(rate_limit.observed_at.elapsed());
+                log::warn!("Rate limit exhausted, waiting {:?}", wait);
+                Ok(Some(wait))
+            }
+        }
+    }
+}
+
+pub(crate) fn header_u32(headers: &HeaderMap, name: &str) -> Option<u32> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use reqwest::StatusCode;
+
+    fn quota_headers(limit: &'static str, remaining: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", HeaderValue::from_static(limit));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static(remaining));
+        headers
+    }
+
+    #[test]
+    fn records_and_enforces_quota() {
+        let tracker = QuotaTracker::default();
+        tracker.record(&quota_headers("30", "1"));
+        assert_eq!(tracker.get().map(|q| (q.limit, q.remaining)), Some((30, 1)));
+        assert!(tracker.check(ThrottlePolicy::Fail).unwrap().is_none());
+
+        tracker.record(&quota_headers("30", "0"));
+        assert!(tracker.check(ThrottlePolicy::Disabled).unwrap().is_none());
+        assert!(matches!(
+            tracker.check(ThrottlePolicy::Fail),
+            Err(Error::RateLimited {
+                remaining: Some(0),
+                ..
+            })
+        ));
+
+        let window = Duration::from_secs(60);
+        let wait = tracker.check(ThrottlePolicy::Block { window }).unwrap();
+        assert!(wait.is_some_and(|wait| wait <= window));
+        assert!(tracker.get().is_none());
+    }
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        let policy = RetryPolicy::exponential(5);
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(10), Duration::from_secs(30));
+        assert_eq!(policy.backoff(2_000), Duration::from_secs(30));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+
+        let headers = HeaderMap::new();
+        assert!(policy
+            .retry_after(0, StatusCode::TOO_MANY_REQUESTS, &headers)
+            .is_some());
+        assert!(policy
+            .retry_after(0, StatusCode::BAD_GATEWAY, &headers)
+            .is_some());
+        assert!(policy
+            .retry_after(0, StatusCode::NOT_FOUND, &headers)
+            .is_none());
+        assert!(policy
+            .retry_after(5, StatusCode::TOO_MANY_REQUESTS, &headers)
+            .is_none());
+        assert!(RetryPolicy::default()
+            .retry_after(0, StatusCode::TOO_MANY_REQUESTS, &headers)
+            .is_none());
+    }
+}
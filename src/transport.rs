@@ -0,0 +1,526 @@
+use crate::error::{Error, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A request about to be sent by a client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Fully built URL, including credentials
+    pub url: String,
+}
+
+impl Request {
+    pub fn get(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+/// A fully read response, as handed to [Spec::parse_response](crate::prelude::Spec::parse_response)
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// A 200 response with a JSON body, handy for mock transports
+    pub fn json_body(body: impl Into<Vec<u8>>) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        Self {
+            status: StatusCode::OK,
+            headers,
+            body: body.into(),
+        }
+    }
+
+    /// Decode the body as JSON
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    /// The body as (lossy) UTF-8
+    pub fn text(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+/// Sends requests on behalf of a blocking [Client](crate::prelude::Client)
+///
+/// Implemented for closures, so a mock can be as small as
+/// `|_: &Request| Ok(Response::json_body("{}"))`
+pub trait Transport: Send + Sync {
+    fn send(&self, request: &Request) -> Result<Response>;
+}
+
+impl<F> Transport for F
+where
+    F: Fn(&Request) -> Result<Response> + Send + Sync,
+{
+    fn send(&self, request: &Request) -> Result<Response> {
+        self(request)
+    }
+}
+
+/// Boxed future returned by [AsyncTransport::send]
+#[cfg(feature = "async")]
+pub type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
+/// Sends requests on behalf of an [AsyncClient](crate::prelude::AsyncClient)
+#[cfg(feature = "async")]
+pub trait AsyncTransport: Send + Sync {
+    fn send<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response>>;
+}
+
+/// Default transport: a pooled blocking reqwest client
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransport {
+    http: reqwest::blocking::Client,
+}
+
+impl HttpTransport {
+    pub fn new(http: reqwest::blocking::Client) -> Self {
+        Self { http }
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let response = self.http.get(&request.url).send()?;
+        Ok(Response {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes()?.to_vec(),
+        })
+    }
+}
+
+/// Default async transport: a pooled reqwest client
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Default)]
+pub struct AsyncHttpTransport {
+    http: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl AsyncHttpTransport {
+    pub fn new(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for AsyncHttpTransport {
+    fn send<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            let response = self.http.get(&request.url).send().await?;
+            Ok(Response {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.bytes().await?.to_vec(),
+            })
+        })
+    }
+}
+
+/// How a [Cassette] treats requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Only serve recorded responses; a request without one is an error
+    Replay,
+    /// Always send through the inner transport and (over)write the recording
+    Record,
+    /// Serve recorded responses, recording the ones that are missing
+    ReplayOrRecord,
+}
+
+/// Record/replay transport backed by a directory of JSON files
+///
+/// Each request is stored as one file named after its path and query, with
+/// the `api_key` parameter stripped so recordings never contain credentials
+/// and don't depend on which key recorded them.
+#[derive(Clone)]
+pub struct Cassette {
+    dir: PathBuf,
+    mode: CassetteMode,
+    inner: Option<Arc<dyn Transport>>,
+}
+
+impl Cassette {
+    /// Serve recordings from `dir` without touching the network
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: CassetteMode::Replay,
+            inner: None,
+        }
+    }
+
+    /// Send every request through `inner`, recording responses into `dir`
+    pub fn record(dir: impl Into<PathBuf>, inner: impl Transport + 'static) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: CassetteMode::Record,
+            inner: Some(Arc::new(inner)),
+        }
+    }
+
+    /// Serve recordings from `dir`, sending and recording missing ones through `inner`
+    pub fn replay_or_record(dir: impl Into<PathBuf>, inner: impl Transport + 'static) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: CassetteMode::ReplayOrRecord,
+            inner: Some(Arc::new(inner)),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// The file a request is recorded in
+    pub fn path_for(&self, request: &Request) -> PathBuf {
+        let key = cassette_key(&request.url);
+        let mut slug: String = key
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+                _ => '_',
+            })
+            .collect();
+        slug = slug.trim_matches('_').to_owned();
+        if slug.len() > 120 {
+            slug.truncate(120);
+            slug.push_str(&format!("-{:016x}", fnv1a(key.as_bytes())));
+        }
+        self.dir.join(slug + ".json")
+    }
+
+    fn load(&self, request: &Request) -> Result<Option<Response>> {
+        let path = self.path_for(request);
+        let contents = match std::fs::read(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let interaction: Interaction = serde_json::from_slice(&contents)?;
+        if interaction.url != cassette_key(&request.url) {
+            log::warn!(
+                "{} was recorded for {}, not {}",
+                path.display(),
+                interaction.url,
+                cassette_key(&request.url)
+            );
+            return Ok(None);
+        }
+        log::debug!("Replaying {} from {}", interaction.url, path.display());
+        interaction.into_response().map(Some)
+    }
+
+    fn save(&self, request: &Request, response: &Response) -> Result<()> {
+        let path = self.path_for(request);
+        std::fs::create_dir_all(&self.dir)?;
+        let mut interaction = Interaction::new(cassette_key(&request.url), response);
+        if let Some(key) = api_key(&request.url) {
+            interaction.scrub(key);
+        }
+        std::fs::write(&path, serde_json::to_vec_pretty(&interaction)?)?;
+        log::debug!("Recorded {} into {}", interaction.url, path.display());
+        Ok(())
+    }
+
+    fn missing(&self, request: &Request) -> Error {
+        Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "no recording for {} at {}",
+                cassette_key(&request.url),
+                self.path_for(request).display()
+            ),
+        ))
+    }
+}
+
+impl Transport for Cassette {
+    fn send(&self, request: &Request) -> Result<Response> {
+        if self.mode != CassetteMode::Record {
+            if let Some(response) = self.load(request)? {
+                return Ok(response);
+            }
+        }
+        let Some(inner) = self
+            .inner
+            .as_ref()
+            .filter(|_| self.mode != CassetteMode::Replay)
+        else {
+            return Err(self.missing(request));
+        };
+        let response = inner.send(request)?;
+        self.save(request, &response)?;
+        Ok(response)
+    }
+}
+
+/// Replays recordings; recording requires the blocking client
+#[cfg(feature = "async")]
+impl AsyncTransport for Cassette {
+    fn send<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            match self.load(request)? {
+                Some(response) => Ok(response),
+                None => Err(self.missing(request)),
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for Cassette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cassette")
+            .field("dir", &self.dir)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+/// Headers worth keeping in a recording
+const RECORDED_HEADERS: [&str; 4] = [
+    "content-type",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "retry-after",
+];
+
+/// A recorded request/response pair, as stored on disk
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Interaction {
+    url: String,
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    body: Body,
+}
+
+/// JSON bodies are stored inline so recordings stay readable and editable
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Body {
+    Json(serde_json::Value),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Interaction {
+    fn new(url: String, response: &Response) -> Self {
+        let headers = RECORDED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = response.headers.get(*name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_owned()))
+            })
+            .collect();
+        let body = match serde_json::from_slice(&response.body) {
+            Ok(json) => Body::Json(json),
+            Err(_) => match String::from_utf8(response.body.clone()) {
+                Ok(text) => Body::Text(text),
+                Err(_) => Body::Bytes(response.body.clone()),
+            },
+        };
+        Self {
+            url,
+            status: response.status.as_u16(),
+            headers,
+            body,
+        }
+    }
+
+    /// Some endpoints echo the key back in `links`; never write it to disk
+    fn scrub(&mut self, api_key: &str) {
+        if api_key.is_empty() || api_key == crate::config::DEMO_KEY {
+            return;
+        }
+        let scrubbed = match &self.body {
+            Body::Json(json) => json.to_string(),
+            Body::Text(text) => text.clone(),
+            Body::Bytes(_) => return,
+        };
+        if !scrubbed.contains(api_key) {
+            return;
+        }
+        let scrubbed = scrubbed.replace(api_key, crate::config::DEMO_KEY);
+        self.body = match self.body {
+            Body::Json(_) => {
+                serde_json::from_str(&scrubbed).map_or(Body::Text(scrubbed), Body::Json)
+            }
+            _ => Body::Text(scrubbed),
+        };
+    }
+
+    fn into_response(self) -> Result<Response> {
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|e| Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+        let body = match self.body {
+            Body::Json(json) => serde_json::to_vec(&json)?,
+            Body::Text(text) => text.into_bytes(),
+            Body::Bytes(bytes) => bytes,
+        };
+        Ok(Response {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Path and query of `url` with the origin and `api_key` removed and the
+/// parameters sorted, so equivalent requests share a recording
+pub(crate) fn cassette_key(url: &str) -> String {
+    let without_origin = match url.find("://") {
+        Some(scheme) => {
+            let host = scheme + 3;
+            url[host..].find('/').map_or("", |path| &url[host + path..])
+        }
+        None => url,
+    };
+    let mut parts = without_origin.split(['?', '&']);
+    let path = parts.next().unwrap_or_default().to_owned();
+    let mut params: Vec<&str> = parts
+        .filter(|param| !param.is_empty() && !param.starts_with("api_key="))
+        .collect();
+    params.sort_unstable();
+    if params.is_empty() {
+        path
+    } else {
+        format!("{}?{}", path, params.join("&"))
+    }
+}
+
+/// The `api_key` parameter of `url`, if any
+fn api_key(url: &str) -> Option<&str> {
+    url.split(['?', '&'])
+        .find_map(|param| param.strip_prefix("api_key="))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Cassette used by the crate's tests. Set `NASA_RS_RECORD` to re-record
+/// missing responses against the live API
+#[cfg(test)]
+pub(crate) fn test_cassette() -> Cassette {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes");
+    if std::env::var_os("NASA_RS_RECORD").is_some() {
+        Cassette::replay_or_record(dir, HttpTransport::default())
+    } else {
+        Cassette::replay(dir)
+    }
+}
+
+/// Blocking client replaying [test_cassette]
+#[cfg(test)]
+pub(crate) fn test_client<S: crate::prelude::Spec>() -> crate::prelude::Client<S> {
+    crate::prelude::Client::builder()
+        .transport(test_cassette())
+        .build()
+        .unwrap()
+}
+
+/// Blocking client answering every request with `[]`, and the URLs it was sent
+#[cfg(test)]
+pub(crate) fn recording_client<S: crate::prelude::Spec>() -> (
+    crate::prelude::Client<S>,
+    Arc<std::sync::Mutex<Vec<String>>>,
+) {
+    let urls = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sent = urls.clone();
+    let client = crate::prelude::Client::builder()
+        .api_key("test-key")
+        .transport(move |request: &Request| {
+            sent.lock().unwrap().push(request.url.clone());
+            Ok(Response::json_body("[]"))
+        })
+        .build()
+        .unwrap();
+    (client, urls)
+}
+
+/// A date for test fixtures
+#[cfg(test)]
+pub(crate) fn ymd(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+    chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// Async client replaying [test_cassette]
+#[cfg(all(test, feature = "async"))]
+pub(crate) fn test_async_client<S: crate::prelude::Spec>() -> crate::prelude::AsyncClient<S> {
+    crate::prelude::AsyncClient::builder()
+        .async_transport(test_cassette())
+        .build_async()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cassette_key_strips_credentials_and_sorts() {
+        assert_eq!(
+            cassette_key("https://api.nasa.gov/planetary/apod??hd=true&date=2019-01-01"),
+            "/planetary/apod?date=2019-01-01&hd=true"
+        );
+        assert_eq!(
+            cassette_key("http://localhost:8080/DONKI/FLR&api_key=secret"),
+            "/DONKI/FLR"
+        );
+    }
+
+    #[test]
+    fn cassette_round_trip() {
+        let dir = std::env::temp_dir().join(format!("nasa-rs-cassette-{}", std::process::id()));
+        let request =
+            Request::get("https://api.nasa.gov/planetary/apod?date=2024-01-01&api_key=secret");
+        let recorder = Cassette::record(&dir, |request: &Request| {
+            let body = format!(r#"{{"title":"recorded","self":"{}"}}"#, request.url);
+            Ok(Response::json_body(body))
+        });
+        Transport::send(&recorder, &request).unwrap();
+
+        let contents = std::fs::read_to_string(recorder.path_for(&request)).unwrap();
+        assert!(!contents.contains("secret"));
+
+        let replayed = Transport::send(&Cassette::replay(&dir), &request).unwrap();
+        let body: serde_json::Value = replayed.json().unwrap();
+        assert_eq!(body["title"], "recorded");
+
+        let missing = Request::get("https://api.nasa.gov/planetary/apod?date=1999-01-01");
+        assert!(matches!(
+            Transport::send(&Cassette::replay(&dir), &missing),
+            Err(Error::Io(_))
+        ));
+        std::fs::remove_dir_all(dir).ok();
+    }
+}